@@ -7,37 +7,81 @@
 //! an Array with an ArrayBuilder at compile time. This module also contains examples on how to use
 //! generics around the Array and ArrayBuilder.
 
+mod bytes_array;
 mod dyn_array;
 mod impls;
 mod iterator;
+mod json_array;
 mod list_array;
+mod null_array;
+mod ops;
 mod physical_type;
 mod primitive_array;
 mod string_array;
+mod struct_array;
 
+pub use bytes_array::*;
+pub use dyn_array::{DynArrayBuilder, DynBuilder};
 pub use iterator::*;
+pub use json_array::*;
 pub use list_array::*;
+pub use null_array::*;
+pub use ops::Monotonic;
 pub use physical_type::*;
 pub use primitive_array::*;
 pub use string_array::*;
+pub use struct_array::*;
 
 mod all_arrays {
+    #[cfg(feature = "uuid")]
+    pub use super::UuidArray;
     pub use super::{
-        BoolArray, DecimalArray, F32Array, F64Array, I16Array, I32Array, I64Array, ListArray,
-        StringArray,
+        BoolArray, BytesArray, DateArray, DecimalArray, F32Array, F64Array, I16Array, I32Array,
+        I64Array, JsonArray, ListArray, NullArray, StringArray, TimestampArray,
     };
 }
 
 mod all_array_builders {
+    #[cfg(feature = "uuid")]
+    pub use super::UuidArrayBuilder;
     pub use super::{
-        BoolArrayBuilder, DecimalArrayBuilder, F32ArrayBuilder, F64ArrayBuilder, I16ArrayBuilder,
-        I32ArrayBuilder, I64ArrayBuilder, ListArrayBuilder, StringArrayBuilder,
+        BoolArrayBuilder, BytesArrayBuilder, DateArrayBuilder, DecimalArrayBuilder,
+        F32ArrayBuilder, F64ArrayBuilder, I16ArrayBuilder, I32ArrayBuilder, I64ArrayBuilder,
+        JsonArrayBuilder, ListArrayBuilder, NullArrayBuilder, StringArrayBuilder,
+        TimestampArrayBuilder,
     };
 }
 
+use std::collections::TryReserveError;
+use std::ops::{Bound, Range, RangeBounds};
+
 use crate::scalar::{Scalar, ScalarRef};
 use crate::TypeMismatch;
 
+/// Resolve `range` into `[start, end)` of an array with `len` elements.
+///
+/// Panics if the range is out of bounds or decreasing.
+pub(crate) fn resolve_range(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Unbounded => 0,
+        Bound::Included(&x) => x,
+        Bound::Excluded(&x) => x + 1,
+    };
+    let end = match range.end_bound() {
+        Bound::Unbounded => len,
+        Bound::Included(&x) => x + 1,
+        Bound::Excluded(&x) => x,
+    };
+    assert!(
+        start <= end && end <= len,
+        "slice range {}..{} out of bounds for array of length {}",
+        start,
+        end,
+        len
+    );
+    start..end
+}
+
 /// [`Array`] is a collection of data of the same type.
 pub trait Array:
     Send
@@ -64,9 +108,22 @@ where
     /// the same as `self` for `&str`, we use GAT here.
     type RefItem<'a>: ScalarRef<'a, ScalarType = Self::OwnedItem, ArrayType = Self>;
 
+    /// The physical type of this array, which is available without an instance of the array.
+    const PHYSICAL_TYPE: PhysicalType;
+
     /// Retrieve a reference to value.
     fn get(&self, idx: usize) -> Option<Self::RefItem<'_>>;
 
+    /// Retrieve a reference to value without bounds checks on the data and the null bitmap, which
+    /// is faster than [`Array::get`] in hot loops.
+    ///
+    /// # Safety
+    ///
+    /// `idx` must be less than [`Array::len`]. Otherwise, the behavior is undefined.
+    unsafe fn get_unchecked(&self, idx: usize) -> Option<Self::RefItem<'_>> {
+        self.get(idx)
+    }
+
     /// Number of items of array.
     fn len(&self) -> usize;
 
@@ -75,9 +132,61 @@ where
         self.len() == 0
     }
 
+    /// Check if the value at `idx` is null.
+    fn is_null(&self, idx: usize) -> bool {
+        self.get(idx).is_none()
+    }
+
+    /// Number of nulls in this array.
+    fn null_count(&self) -> usize {
+        (0..self.len()).filter(|&idx| self.is_null(idx)).count()
+    }
+
+    /// Check if there's any null in this array.
+    fn has_nulls(&self) -> bool {
+        self.null_count() != 0
+    }
+
     /// Get iterator of this array.
     fn iter(&self) -> ArrayIterator<Self>;
 
+    /// Copy all items of this array into a vector of owned values.
+    fn to_owned_vec(&self) -> Vec<Option<Self::OwnedItem>> {
+        self.iter()
+            .map(|item| item.map(|item| item.to_owned_scalar()))
+            .collect()
+    }
+
+    /// Keep elements where `mask` is true. A null in `mask` is treated as false.
+    ///
+    /// Panics if `mask` is not of the same length as the array.
+    fn filter(&self, mask: &BoolArray) -> Self {
+        assert_eq!(mask.len(), self.len(), "mask length mismatch");
+        let mut builder = Self::Builder::with_capacity(self.len());
+        for (value, selected) in self.iter().zip(mask.iter()) {
+            if selected == Some(true) {
+                builder.push(value);
+            }
+        }
+        builder.finish()
+    }
+
+    /// Build a new array of type `B` by applying `f` to each element, including nulls.
+    ///
+    /// `f` returns an owned value, so that it may produce values that don't borrow from `self`,
+    /// e.g., formatting an `i32` into a `String`.
+    fn map_scalar<B: Array>(
+        &self,
+        f: impl Fn(Option<Self::RefItem<'_>>) -> Option<B::OwnedItem>,
+    ) -> B {
+        let mut builder = B::Builder::with_capacity(self.len());
+        for item in self.iter() {
+            let output = f(item);
+            builder.push(output.as_ref().map(|x| x.as_scalar_ref()));
+        }
+        builder.finish()
+    }
+
     /// Build array from slice
     fn from_slice(data: &[Option<Self::RefItem<'_>>]) -> Self {
         let mut builder = Self::Builder::with_capacity(data.len());
@@ -86,6 +195,17 @@ where
         }
         builder.finish()
     }
+
+    /// Build array from an iterator. The builder is pre-allocated with the lower bound of
+    /// [`Iterator::size_hint`].
+    fn from_ref_iter<'a>(iter: impl IntoIterator<Item = Option<Self::RefItem<'a>>>) -> Self {
+        let iter = iter.into_iter();
+        let mut builder = Self::Builder::with_capacity(iter.size_hint().0);
+        for item in iter {
+            builder.push(item);
+        }
+        builder.finish()
+    }
 }
 
 /// [`ArrayBuilder`] builds an [`Array`].
@@ -103,6 +223,53 @@ pub trait ArrayBuilder {
     /// Append a value to builder.
     fn push(&mut self, value: Option<<Self::Array as Array>::RefItem<'_>>);
 
+    /// Number of values pushed so far.
+    fn len(&self) -> usize;
+
+    /// Indicates whether no value has been pushed.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Try to reserve capacity for at least `additional` more values, returning an error instead
+    /// of aborting if the allocation fails.
+    ///
+    /// The null bitmap is only 1/64 the size of the data, and is still reserved infallibly.
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError>;
+
+    /// Reserve capacity for at least `additional` more values.
+    ///
+    /// Panics if the allocation fails.
+    fn reserve(&mut self, additional: usize) {
+        self.try_reserve(additional).expect("failed to reserve");
+    }
+
+    /// Append an owned value, e.g., a `String` for [`StringArrayBuilder`], without borrowing it
+    /// as the ref type first.
+    fn push_owned(&mut self, value: Option<<Self::Array as Array>::OwnedItem>) {
+        self.push(value.as_ref().map(|v| v.as_scalar_ref()));
+    }
+
+    /// Append all values of `iter`, reserving for the lower bound of its size hint up front.
+    fn extend<'a, I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Option<<Self::Array as Array>::RefItem<'a>>>,
+    {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+
+    /// Append `value` for `n` times, e.g., to materialize a constant.
+    fn append_n(&mut self, n: usize, value: Option<<Self::Array as Array>::RefItem<'_>>) {
+        self.reserve(n);
+        for _ in 0..n {
+            self.push(value);
+        }
+    }
+
     /// Finish build and return a new array.
     fn finish(self) -> Self::Array;
 }
@@ -117,8 +284,51 @@ pub enum ArrayImpl {
     Float64(F64Array),
     Bool(BoolArray),
     String(StringArray),
+    Bytes(BytesArray),
     Decimal(DecimalArray),
+    Date32(DateArray),
+    Timestamp(TimestampArray),
     List(ListArray),
+    Json(JsonArray),
+    Null(NullArray),
+    #[cfg(feature = "uuid")]
+    Uuid(UuidArray),
+}
+
+impl ArrayImpl {
+    /// Get a wrapper that prints the array as a column, e.g., for CLI output. See
+    /// [`ArrayImplDisplay`].
+    pub fn display(&self) -> ArrayImplDisplay<'_> {
+        ArrayImplDisplay(self)
+    }
+
+    /// Get the value at `idx` as the scalar type `S`, e.g., `i32` or `String`, without matching
+    /// on the variants of [`ScalarRefImpl`](crate::scalar::ScalarRefImpl). Returns an error if
+    /// the array doesn't store `S`.
+    pub fn try_get<S: Scalar>(&self, idx: usize) -> Result<Option<S::RefType<'_>>, TypeMismatch>
+    where
+        for<'a> &'a S::ArrayType: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+    {
+        let array: &S::ArrayType = self.try_into()?;
+        Ok(array.get(idx))
+    }
+}
+
+/// Prints an array as a column, with one value per line and `NULL` for nulls. Values are printed
+/// with the [`Display`](std::fmt::Display) of [`ScalarRefImpl`](crate::scalar::ScalarRefImpl),
+/// so strings are not quoted. Created by [`ArrayImpl::display`].
+pub struct ArrayImplDisplay<'a>(&'a ArrayImpl);
+
+impl<'a> std::fmt::Display for ArrayImplDisplay<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for idx in 0..self.0.len() {
+            match self.0.get(idx) {
+                Some(value) => writeln!(f, "{}", value)?,
+                None => writeln!(f, "NULL")?,
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -130,8 +340,15 @@ pub enum ArrayImplRef<'a> {
     Float64(&'a F64Array),
     Bool(&'a BoolArray),
     String(&'a StringArray),
+    Bytes(&'a BytesArray),
     Decimal(&'a DecimalArray),
+    Date32(&'a DateArray),
+    Timestamp(&'a TimestampArray),
     List(&'a ListArray),
+    Json(&'a JsonArray),
+    Null(&'a NullArray),
+    #[cfg(feature = "uuid")]
+    Uuid(&'a UuidArray),
 }
 
 /// The boxed array type.
@@ -147,8 +364,15 @@ pub enum ArrayBuilderImpl {
     Float64(F64ArrayBuilder),
     Bool(BoolArrayBuilder),
     String(StringArrayBuilder),
+    Bytes(BytesArrayBuilder),
     Decimal(DecimalArrayBuilder),
+    Date32(DateArrayBuilder),
+    Timestamp(TimestampArrayBuilder),
     List(ListArrayBuilder),
+    Json(JsonArrayBuilder),
+    Null(NullArrayBuilder),
+    #[cfg(feature = "uuid")]
+    Uuid(UuidArrayBuilder),
 }
 
 #[cfg(test)]
@@ -194,6 +418,135 @@ mod tests {
         check_array_eq(&array, &data[..]);
     }
 
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_build_uuid_array() {
+        use uuid::Uuid;
+
+        use crate::scalar::ScalarRefImpl;
+
+        let a = Uuid::from_u128(0x1234_5678_9abc_def0_1234_5678_9abc_def0);
+        let b = Uuid::from_u128(0xffff_0000_ffff_0000_ffff_0000_ffff_0000);
+        let data = vec![Some(a), None, Some(b)];
+        let array = UuidArray::from_slice(&data);
+        check_array_eq(&array, &data);
+        assert_eq!(array.len(), 3);
+
+        let array: ArrayImpl = array.into();
+        assert_eq!(array.physical_type(), PhysicalType::Uuid);
+        assert_eq!(array.get(0), Some(ScalarRefImpl::Uuid(a)));
+        assert_eq!(array.get(1), None);
+        assert_ne!(array.get(0), array.get(2));
+        assert!(a < b);
+    }
+
+    const _: () = assert!(matches!(I32Array::PHYSICAL_TYPE, PhysicalType::Int32));
+
+    /// Get the physical type of an array type, without an instance of the array.
+    fn physical_type_of<A: Array>() -> PhysicalType {
+        A::PHYSICAL_TYPE
+    }
+
+    #[test]
+    fn test_physical_type_const() {
+        assert_eq!(physical_type_of::<I32Array>(), PhysicalType::Int32);
+        assert_eq!(physical_type_of::<StringArray>(), PhysicalType::String);
+        assert_eq!(physical_type_of::<ListArray>(), PhysicalType::List);
+        assert_eq!(physical_type_of::<DecimalArray>(), PhysicalType::Decimal);
+    }
+
+    #[test]
+    fn test_build_date_array() {
+        use crate::scalar::{Date32, ScalarRefImpl};
+
+        let dates = [Date32::from_ymd(2022, 3, 9), None, Some(Date32(0))];
+        let array = DateArray::from_slice(&dates);
+        assert_eq!(array.iter().collect::<Vec<_>>(), dates);
+
+        let array: ArrayImpl = array.into();
+        assert_eq!(array.physical_type(), PhysicalType::Date32);
+        assert_eq!(array.get(2), Some(ScalarRefImpl::Date32(Date32(0))));
+        assert_eq!(
+            format!("{:?}", array),
+            "Date32([Some(2022-03-09), None, Some(1970-01-01)])"
+        );
+    }
+
+    #[test]
+    fn test_build_timestamp_array() {
+        use crate::scalar::Timestamp;
+
+        let mut builder = TimestampArrayBuilder::with_capacity(3);
+        builder.push(Some(Timestamp(0)));
+        builder.push(None);
+        builder.push(Some(Timestamp(1_646_829_296_000_000)));
+        let array = builder.finish();
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![
+                Some(Timestamp(0)),
+                None,
+                Some(Timestamp(1_646_829_296_000_000))
+            ]
+        );
+        assert_eq!(
+            format!("{:?}", array),
+            "[Some(1970-01-01T00:00:00), None, Some(2022-03-09T12:34:56)]"
+        );
+    }
+
+    #[test]
+    fn test_to_owned_vec() {
+        let array = I32Array::from_slice(&[Some(1), None, Some(3)]);
+        assert_eq!(array.to_owned_vec(), vec![Some(1), None, Some(3)]);
+
+        let array = StringArray::from_slice(&[Some("a"), None, Some("")]);
+        assert_eq!(
+            array.to_owned_vec(),
+            vec![Some("a".to_string()), None, Some("".to_string())]
+        );
+
+        assert!(StringArray::from_slice(&[]).to_owned_vec().is_empty());
+        assert!(I32Array::from_slice(&[]).to_owned_vec().is_empty());
+    }
+
+    #[test]
+    fn test_map_scalar() {
+        let array = I32Array::from_slice(&[Some(1), None, Some(-3)]);
+        let result: StringArray = array.map_scalar(|x| x.map(|v| v.to_string()));
+        assert_eq!(
+            result.to_owned_vec(),
+            vec![Some("1".to_string()), None, Some("-3".to_string())]
+        );
+
+        // Nulls are passed to `f` as well.
+        let result: I64Array = array.map_scalar(|x| Some(x.map_or(0, |v| v as i64 * 2)));
+        assert_eq!(result.to_owned_vec(), vec![Some(2), Some(0), Some(-6)]);
+    }
+
+    #[test]
+    fn test_default_null_count() {
+        let array = BytesArray::from_slice(&[None, Some(b"a"), None, Some(b"")]);
+        assert_eq!(array.null_count(), 2);
+        assert!(array.is_null(2));
+        assert!(!array.is_null(3));
+    }
+
+    #[test]
+    fn test_builder_try_reserve() {
+        // Reserving a modest amount succeeds. An allocation that can never be satisfied returns an
+        // error instead of aborting.
+        let mut builder = I32ArrayBuilder::with_capacity(0);
+        builder.try_reserve(1024).unwrap();
+        assert!(builder.try_reserve(usize::MAX).is_err());
+        builder.push(Some(1));
+        assert_eq!(builder.finish().len(), 1);
+
+        let mut builder: ArrayBuilderImpl = StringArrayBuilder::with_capacity(0).into();
+        builder.try_reserve(1024).unwrap();
+        assert!(builder.try_reserve(usize::MAX).is_err());
+    }
+
     fn add_i32(i1: i32, i2: i32) -> i32 {
         i1 + i2
     }
@@ -233,4 +586,38 @@ mod tests {
             assert_eq!(err.1, PhysicalType::String);
         }
     }
+
+    #[test]
+    fn test_array_display() {
+        use rust_decimal::Decimal;
+
+        let array: ArrayImpl = DecimalArray::from_slice(&[
+            Some(Decimal::new(1050, 2)),
+            None,
+            Some(Decimal::new(-3, 0)),
+        ])
+        .into();
+        assert_eq!(array.display().to_string(), "10.50\nNULL\n-3\n");
+
+        let array: ArrayImpl = StringArray::from_slice(&[Some("a b"), Some(""), None]).into();
+        assert_eq!(array.display().to_string(), "a b\n\nNULL\n");
+
+        let array: ArrayImpl = I32Array::from_slice(&[]).into();
+        assert_eq!(array.display().to_string(), "");
+    }
+
+    #[test]
+    fn test_try_get() {
+        let array: ArrayImpl = I32Array::from_slice(&[Some(1), None]).into();
+        assert_eq!(array.try_get::<i32>(0).unwrap(), Some(1));
+        assert_eq!(array.try_get::<i32>(1).unwrap(), None);
+        assert!(matches!(
+            array.try_get::<String>(0),
+            Err(TypeMismatch(PhysicalType::String, PhysicalType::Int32))
+        ));
+
+        let array: ArrayImpl = StringArray::from_slice(&[Some("abc")]).into();
+        assert_eq!(array.try_get::<String>(0).unwrap(), Some("abc"));
+        assert!(array.try_get::<i64>(0).is_err());
+    }
 }
@@ -0,0 +1,125 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Binary array and array builders.
+//!
+//! [`BytesArray`] has the same layout as [`StringArray`](super::StringArray), but its elements are
+//! raw bytes that may not be valid UTF-8.
+
+use std::collections::TryReserveError;
+
+use bitvec::prelude::BitVec;
+
+use super::{Array, ArrayBuilder, ArrayIterator, PhysicalType};
+
+/// An [`Array`] that stores [`Vec<u8>`]
+#[derive(Clone)]
+pub struct BytesArray {
+    /// The flattened data of all values.
+    data: Vec<u8>,
+
+    /// Offsets of each value in the data flat array.
+    offsets: Vec<usize>,
+
+    /// The null bitmap of this array.
+    bitmap: BitVec,
+}
+
+impl Array for BytesArray {
+    type Builder = BytesArrayBuilder;
+
+    type OwnedItem = Vec<u8>;
+
+    type RefItem<'a> = &'a [u8];
+
+    const PHYSICAL_TYPE: PhysicalType = PhysicalType::Bytes;
+
+    fn get(&self, idx: usize) -> Option<&[u8]> {
+        if self.bitmap[idx] {
+            Some(&self.data[self.offsets[idx]..self.offsets[idx + 1]])
+        } else {
+            None
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.bitmap.len()
+    }
+
+    fn iter(&self) -> ArrayIterator<Self> {
+        ArrayIterator::new(self)
+    }
+}
+
+/// [`ArrayBuilder`] for [`Vec<u8>`].
+pub struct BytesArrayBuilder {
+    /// The flattened data of all values.
+    data: Vec<u8>,
+
+    /// Offsets of each value in the data flat array.
+    offsets: Vec<usize>,
+
+    /// The null bitmap of this array.
+    bitmap: BitVec,
+}
+
+impl ArrayBuilder for BytesArrayBuilder {
+    type Array = BytesArray;
+
+    fn with_capacity(capacity: usize) -> Self {
+        let mut offsets = Vec::with_capacity(capacity + 1);
+        offsets.push(0);
+        Self {
+            data: Vec::with_capacity(capacity),
+            bitmap: BitVec::with_capacity(capacity),
+            offsets,
+        }
+    }
+
+    fn push(&mut self, value: Option<&[u8]>) {
+        if let Some(v) = value {
+            self.data.extend_from_slice(v);
+        }
+        self.offsets.push(self.data.len());
+        self.bitmap.push(value.is_some());
+    }
+
+    fn len(&self) -> usize {
+        self.bitmap.len()
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.offsets.try_reserve(additional)?;
+        self.data.try_reserve(additional)?;
+        self.bitmap.reserve(additional);
+        Ok(())
+    }
+
+    fn finish(self) -> Self::Array {
+        BytesArray {
+            data: self.data,
+            bitmap: self.bitmap,
+            offsets: self.offsets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::ArrayImpl;
+    use crate::scalar::ScalarRefImpl;
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let values: [Option<&[u8]>; 4] = [Some(&[0xff, 0x00]), None, Some(b""), Some(b"abc")];
+        let array = BytesArray::from_slice(&values);
+        assert_eq!(array.iter().collect::<Vec<_>>(), values);
+
+        let array = ArrayImpl::from(array);
+        let value = array.get(0).unwrap();
+        assert_eq!(value, ScalarRefImpl::Bytes(&[0xff, 0x00]));
+        assert_eq!(value.to_string(), "\\xff00");
+        let value: &[u8] = value.try_into().unwrap();
+        assert_eq!(value, &[0xff, 0x00]);
+    }
+}
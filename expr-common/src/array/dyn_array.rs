@@ -8,6 +8,7 @@ use super::{
 };
 use crate::macros::for_all_variants;
 use crate::scalar::ScalarRefImpl;
+use crate::TypeMismatch;
 
 pub trait PhysicalTypeOf {
     fn physical_type(&self) -> PhysicalType;
@@ -70,6 +71,70 @@ where
     }
 }
 
+/// The object-safe array builder trait, which builds a [`BoxedArray`].
+///
+/// Different from [`ArrayBuilderImpl`], builders of new array types don't need to be added to an
+/// enum.
+pub trait DynArrayBuilder: Send + Sync {
+    /// Append a value. Returns an error if the value is not of the same physical type as the
+    /// builder, in which case nothing is appended.
+    fn try_push(&mut self, value: Option<ScalarRefImpl<'_>>) -> Result<(), TypeMismatch>;
+
+    /// Append a value. Panics if the value is not of the same physical type as the builder.
+    fn push(&mut self, value: Option<ScalarRefImpl<'_>>) {
+        if let Err(err) = self.try_push(value) {
+            panic!("failed to push to array: {}", err);
+        }
+    }
+
+    /// Number of values pushed so far.
+    fn len(&self) -> usize;
+
+    /// Indicates whether no value has been pushed.
+    fn is_empty(&self) -> bool;
+
+    /// Get physical type of the array being built.
+    fn physical_type(&self) -> PhysicalType;
+
+    /// Finish build and return a new array.
+    fn finish(self: Box<Self>) -> BoxedArray;
+}
+
+/// Wraps an [`ArrayBuilder`] so that it can be used as a [`DynArrayBuilder`].
+///
+/// The wrapper keeps methods of [`DynArrayBuilder`] from clashing with those of [`ArrayBuilder`]
+/// on concrete builders.
+pub struct DynBuilder<B>(pub B);
+
+impl<B> DynArrayBuilder for DynBuilder<B>
+where
+    B: ArrayBuilder + Send + Sync,
+    B::Array: DynArray,
+    for<'a> <B::Array as Array>::RefItem<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>,
+{
+    fn try_push(&mut self, value: Option<ScalarRefImpl<'_>>) -> Result<(), TypeMismatch> {
+        let value = value.map(TryInto::try_into).transpose()?;
+        self.0.push(value);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn physical_type(&self) -> PhysicalType {
+        <B::Array as Array>::PHYSICAL_TYPE
+    }
+
+    fn finish(self: Box<Self>) -> BoxedArray {
+        BoxedArray(Box::new(self.0.finish()))
+    }
+}
+
 impl BoxedArray {
     /// Get the array builder of the current array.
     pub fn new_builder(&self, capacity: usize) -> ArrayBuilderImpl {
@@ -90,6 +155,21 @@ impl BoxedArray {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Get physical type of the current array.
+    pub fn physical_type(&self) -> PhysicalType {
+        self.0.physical_type()
+    }
+
+    /// Get iterator of this array.
+    pub fn iter(&self) -> impl Iterator<Item = Option<ScalarRefImpl<'_>>> {
+        (0..self.len()).map(|idx| self.get(idx))
+    }
+
+    /// Downcast to a concrete array type. Returns `None` if the array is not of type `A`.
+    pub fn as_array<A: Array>(&self) -> Option<&A> {
+        self.0.as_any().downcast_ref::<A>()
+    }
 }
 
 impl Clone for BoxedArray {
@@ -157,6 +237,7 @@ for_all_variants! { impl_boxed_array_dispatch }
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::array::{I32ArrayBuilder, StringArrayBuilder};
 
     #[test]
     fn test_create_boxed_array() {
@@ -166,4 +247,56 @@ mod tests {
         let a = a.into_array_impl();
         assert_eq!(a.get(0), Some(ScalarRefImpl::Int32(1)));
     }
+
+    #[test]
+    fn test_boxed_array_downcast() {
+        let a: ArrayImpl = I32Array::from_slice(&[Some(1), None]).into();
+        let a = a.into_boxed_array();
+        let array = a.as_array::<I32Array>().unwrap();
+        assert_eq!(array.iter().collect::<Vec<_>>(), vec![Some(1), None]);
+        assert!(a.as_array::<StringArray>().is_none());
+    }
+
+    #[test]
+    fn test_dyn_array_builder() {
+        let mut builders: Vec<Box<dyn DynArrayBuilder>> = vec![
+            Box::new(DynBuilder(I32ArrayBuilder::with_capacity(0))),
+            Box::new(DynBuilder(StringArrayBuilder::with_capacity(0))),
+        ];
+        builders[0].push(Some(ScalarRefImpl::Int32(1)));
+        builders[0].push(None);
+        builders[1].push(Some(ScalarRefImpl::String("a")));
+        assert!(builders[1].try_push(Some(ScalarRefImpl::Int32(1))).is_err());
+        assert_eq!(builders[1].len(), 1);
+        assert_eq!(builders[1].physical_type(), PhysicalType::String);
+
+        let arrays = builders
+            .into_iter()
+            .map(|builder| builder.finish())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            arrays[0]
+                .as_array::<I32Array>()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![Some(1), None]
+        );
+        assert_eq!(arrays[1].get(0), Some(ScalarRefImpl::String("a")));
+    }
+
+    #[test]
+    fn test_boxed_array_iter() {
+        let a: ArrayImpl = I32Array::from_slice(&[Some(1), None, Some(3)]).into();
+        let a = a.into_boxed_array();
+        assert_eq!(a.physical_type(), PhysicalType::Int32);
+        assert_eq!(
+            a.iter().collect::<Vec<_>>(),
+            vec![
+                Some(ScalarRefImpl::Int32(1)),
+                None,
+                Some(ScalarRefImpl::Int32(3))
+            ]
+        );
+    }
 }
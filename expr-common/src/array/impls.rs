@@ -4,10 +4,17 @@
 
 //! Contains all macro-generated implementations of array methods
 
+use std::collections::TryReserveError;
+use std::ops::RangeBounds;
+
+use bitvec::vec::BitVec;
+
 use crate::array::all_array_builders::*;
 use crate::array::all_arrays::*;
-use crate::array::{Array, ArrayBuilder, ArrayBuilderImpl, ArrayImpl, ArrayImplRef, PhysicalType};
-use crate::macros::for_all_variants;
+use crate::array::{
+    resolve_range, Array, ArrayBuilder, ArrayBuilderImpl, ArrayImpl, ArrayImplRef, PhysicalType,
+};
+use crate::macros::{for_all_primitive_variants, for_all_variants};
 use crate::scalar::*;
 use crate::TypeMismatch;
 
@@ -24,6 +31,19 @@ macro_rules! impl_array_dispatch {
                 }
             }
 
+            /// Create an array of `len` copies of `value`.
+            pub fn broadcast(value: ScalarRefImpl<'_>, len: usize) -> ArrayImpl {
+                match value {
+                    $(
+                        ScalarRefImpl::$Abc(v) => {
+                            let mut builder = <$AbcArrayBuilder>::with_capacity(len);
+                            builder.append_n(len, Some(v));
+                            ArrayImpl::$Abc(builder.finish())
+                        }
+                    )*
+                }
+            }
+
             /// Get the value at the given index.
             pub fn get(&self, idx: usize) -> Option<ScalarRefImpl<'_>> {
                 match self {
@@ -33,6 +53,21 @@ macro_rules! impl_array_dispatch {
                 }
             }
 
+            /// Get owned values at the given indices.
+            ///
+            /// Different from [`ArrayImpl::get`], the variant is matched only once for all indices,
+            /// and the returned values could outlive the array.
+            pub fn get_owned_many(&self, indices: &[usize]) -> Vec<Option<ScalarImpl>> {
+                match self {
+                    $(
+                        Self::$Abc(array) => indices
+                            .iter()
+                            .map(|idx| array.get(*idx).map(|v| ScalarImpl::$Abc(v.to_owned_scalar())))
+                            .collect(),
+                    )*
+                }
+            }
+
             /// Number of items of array.
             pub fn len(&self) -> usize {
                 match self {
@@ -59,24 +94,222 @@ macro_rules! impl_array_dispatch {
                     )*
                 }
             }
+
+            /// Keep elements where `mask` is true. A null in `mask` is treated as false. See
+            /// [`Array::filter`].
+            pub fn filter(&self, mask: &BoolArray) -> ArrayImpl {
+                match self {
+                    $(
+                        Self::$Abc(a) => a.filter(mask).into(),
+                    )*
+                }
+            }
+
+            /// Replace each null with the nearest preceding non-null value. Leading nulls stay
+            /// null.
+            pub fn fill_forward(&self) -> ArrayImpl {
+                match self {
+                    $(
+                        Self::$Abc(a) => fill_forward(a).into(),
+                    )*
+                }
+            }
+
+            /// Replace each null with the nearest following non-null value. Trailing nulls stay
+            /// null.
+            pub fn fill_backward(&self) -> ArrayImpl {
+                match self {
+                    $(
+                        Self::$Abc(a) => fill_backward(a).into(),
+                    )*
+                }
+            }
         }
     }
 }
 
 for_all_variants! { impl_array_dispatch }
 
+fn fill_forward<A: Array>(array: &A) -> A {
+    let mut builder = A::Builder::with_capacity(array.len());
+    let mut last = None;
+    for value in array.iter() {
+        if value.is_some() {
+            last = value;
+        }
+        builder.push(last);
+    }
+    builder.finish()
+}
+
+fn fill_backward<A: Array>(array: &A) -> A {
+    let mut filled = Vec::with_capacity(array.len());
+    let mut next = None;
+    for idx in (0..array.len()).rev() {
+        let value = array.get(idx);
+        if value.is_some() {
+            next = value;
+        }
+        filled.push(next);
+    }
+    let mut builder = A::Builder::with_capacity(array.len());
+    for value in filled.into_iter().rev() {
+        builder.push(value);
+    }
+    builder.finish()
+}
+
+/// Implements methods of [`ArrayImpl`] with fast paths for primitive arrays and string arrays
+macro_rules! impl_array_slice {
+    ([], $( { $Abc:ident, $abc:ident, $AbcArray:ty, $AbcArrayBuilder:ty, $Owned:ty, $Ref:ty } ),*) => {
+        impl ArrayImpl {
+            /// Get elements in `range` as a new array.
+            ///
+            /// Primitive arrays and string arrays are sliced directly. Other arrays are rebuilt
+            /// from the elements in `range`. Panics if `range` is out of bounds.
+            pub fn slice(&self, range: impl RangeBounds<usize>) -> ArrayImpl {
+                let range = resolve_range(range, self.len());
+                match self {
+                    $(
+                        Self::$Abc(array) => array.slice(range).into(),
+                    )*
+                    Self::String(array) => array.slice(range).into(),
+                    _ => self.take(&range.collect::<Vec<_>>()),
+                }
+            }
+
+            /// Build a new array from the elements at `indices`. An index may appear more than
+            /// once.
+            ///
+            /// Primitive arrays and string arrays are gathered directly. Other arrays are rebuilt
+            /// element by element. Panics if any index is out of bounds.
+            pub fn take(&self, indices: &[usize]) -> ArrayImpl {
+                match self {
+                    $(
+                        Self::$Abc(array) => array.take(indices).into(),
+                    )*
+                    Self::String(array) => array.take(indices).into(),
+                    _ => self.take_by_builder(indices),
+                }
+            }
+
+            /// Get the null bitmap of primitive arrays and string arrays. Returns `None` for other
+            /// arrays.
+            pub(crate) fn null_bitmap(&self) -> Option<&BitVec> {
+                match self {
+                    $(
+                        Self::$Abc(array) => Some(array.null_bitmap()),
+                    )*
+                    Self::String(array) => Some(array.null_bitmap()),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+for_all_primitive_variants! { impl_array_slice }
+
 /// Implements dispatch functions for [`ArrayBuilder`]
 macro_rules! impl_array_builder_dispatch {
     ([], $( { $Abc:ident, $abc:ident, $AbcArray:ty, $AbcArrayBuilder:ty, $Owned:ty, $Ref:ty } ),*) => {
         impl ArrayBuilderImpl {
-            /// Appends an element to the back of array.
-            pub fn push(&mut self, v: Option<ScalarRefImpl<'_>>) {
+            /// Appends an element to the back of array. Returns an error if the element is not of
+            /// the same physical type as the builder, in which case nothing is appended.
+            pub fn try_push(&mut self, v: Option<ScalarRefImpl<'_>>) -> Result<(), TypeMismatch> {
                 match (self, v) {
                     $(
                         (Self::$Abc(a), Some(ScalarRefImpl::$Abc(v))) => a.push(Some(v)),
                         (Self::$Abc(a), None) => a.push(None),
                     )*
-                    (a, Some(b)) => Err(TypeMismatch(a.physical_type(), b.physical_type())).unwrap(),
+                    (a, Some(b)) => return Err(TypeMismatch(a.physical_type(), b.physical_type())),
+                }
+                Ok(())
+            }
+
+            /// Appends an element to the back of array. Panics if the element is not of the same
+            /// physical type as the builder. See [`ArrayBuilderImpl::try_push`].
+            pub fn push(&mut self, v: Option<ScalarRefImpl<'_>>) {
+                if let Err(err) = self.try_push(v) {
+                    panic!("failed to push to array: {}", err);
+                }
+            }
+
+            /// Appends `n` copies of an element to the back of array. Returns an error if the element
+            /// is not of the same physical type as the builder, in which case nothing is appended.
+            pub fn try_append_n(
+                &mut self,
+                n: usize,
+                v: Option<ScalarRefImpl<'_>>,
+            ) -> Result<(), TypeMismatch> {
+                match (self, v) {
+                    $(
+                        (Self::$Abc(a), Some(ScalarRefImpl::$Abc(v))) => a.append_n(n, Some(v)),
+                        (Self::$Abc(a), None) => a.append_n(n, None),
+                    )*
+                    (a, Some(b)) => return Err(TypeMismatch(a.physical_type(), b.physical_type())),
+                }
+                Ok(())
+            }
+
+            /// Appends `n` copies of an element to the back of array. Panics if the element is not
+            /// of the same physical type as the builder. See [`ArrayBuilderImpl::try_append_n`].
+            pub fn append_n(&mut self, n: usize, v: Option<ScalarRefImpl<'_>>) {
+                if let Err(err) = self.try_append_n(n, v) {
+                    panic!("failed to push to array: {}", err);
+                }
+            }
+
+            /// Appends an owned element to the back of array. Returns an error if the element is not
+            /// of the same physical type as the builder, in which case nothing is appended.
+            pub fn try_push_owned(&mut self, v: Option<ScalarImpl>) -> Result<(), TypeMismatch> {
+                match (self, v) {
+                    $(
+                        (Self::$Abc(a), Some(ScalarImpl::$Abc(v))) => a.push_owned(Some(v)),
+                        (Self::$Abc(a), None) => a.push_owned(None),
+                    )*
+                    (a, Some(b)) => return Err(TypeMismatch(a.physical_type(), b.physical_type())),
+                }
+                Ok(())
+            }
+
+            /// Appends an owned element to the back of array. Panics if the element is not of the
+            /// same physical type as the builder. See [`ArrayBuilderImpl::try_push_owned`].
+            pub fn push_owned(&mut self, v: Option<ScalarImpl>) {
+                if let Err(err) = self.try_push_owned(v) {
+                    panic!("failed to push to array: {}", err);
+                }
+            }
+
+            /// Appends the element at `idx` of `src`. Returns an error if `src` is not of the
+            /// same physical type as the builder.
+            pub fn push_from(&mut self, src: &ArrayImpl, idx: usize) -> Result<(), TypeMismatch> {
+                match (self, src) {
+                    $(
+                        (Self::$Abc(a), ArrayImpl::$Abc(src)) => {
+                            a.push(src.get(idx));
+                            Ok(())
+                        }
+                    )*
+                    (a, src) => Err(TypeMismatch(a.physical_type(), src.physical_type())),
+                }
+            }
+
+            /// Try to reserve capacity for at least `additional` more elements.
+            pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+                match self {
+                    $(
+                        Self::$Abc(a) => a.try_reserve(additional),
+                    )*
+                }
+            }
+
+            /// Create a new builder of `physical_type` with `capacity`.
+            pub fn with_capacity(physical_type: PhysicalType, capacity: usize) -> Self {
+                match physical_type {
+                    $(
+                        PhysicalType::$Abc => Self::$Abc(<$AbcArrayBuilder>::with_capacity(capacity)),
+                    )*
                 }
             }
 
@@ -89,6 +322,20 @@ macro_rules! impl_array_builder_dispatch {
                 }
             }
 
+            /// Number of values pushed so far.
+            pub fn len(&self) -> usize {
+                match self {
+                    $(
+                        Self::$Abc(a) => a.len(),
+                    )*
+                }
+            }
+
+            /// Indicates whether no value has been pushed.
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
             /// Get physical type of the current array builder
             pub fn physical_type(&self) -> PhysicalType {
                 match self {
@@ -206,6 +453,34 @@ macro_rules! impl_array_debug {
 
 for_all_variants! { impl_array_debug }
 
+fn array_eq<'a, A: Array>(a: &'a A, b: &'a A) -> bool
+where
+    A::RefItem<'a>: PartialEq,
+{
+    a.len() == b.len() && a.iter().eq(b.iter())
+}
+
+/// Implements [`PartialEq`] for [`ArrayImpl`]. Arrays of different physical types are never
+/// equal.
+macro_rules! impl_array_eq {
+    (
+        [], $({ $Abc:ident, $abc:ident, $AbcArray:ty, $AbcArrayBuilder:ty, $Owned:ty, $Ref:ty }),*
+    ) => {
+        impl PartialEq for ArrayImpl {
+            fn eq(&self, other: &Self) -> bool {
+                match (self, other) {
+                    $(
+                        (Self::$Abc(a), Self::$Abc(b)) => array_eq(a, b),
+                    )*
+                    _ => false,
+                }
+            }
+        }
+    };
+}
+
+for_all_variants! { impl_array_eq }
+
 /// Implements `physical_type` for [`Array`]
 macro_rules! impl_physical_type {
     (
@@ -214,13 +489,13 @@ macro_rules! impl_physical_type {
         $(
             impl $AbcArray {
                 fn physical_type(&self) -> PhysicalType {
-                    PhysicalType::$Abc
+                    <$AbcArray as Array>::PHYSICAL_TYPE
                 }
             }
 
             impl $AbcArrayBuilder {
                 fn physical_type(&self) -> PhysicalType {
-                    PhysicalType::$Abc
+                    <$AbcArray as Array>::PHYSICAL_TYPE
                 }
             }
         )*
@@ -4,19 +4,37 @@
 
 use std::iter::TrustedLen;
 
+use super::all_arrays::*;
 use super::Array;
+use crate::macros::for_all_variants;
 
 /// An iterator that iterators on any [`Array`] type.
+///
+/// Besides [`Array::iter`], the iterator can be created by looping over a reference of an array:
+///
+/// ```
+/// use expr_common::array::{Array, I32Array};
+///
+/// let array = I32Array::from_slice(&[Some(1), None, Some(3)]);
+/// let mut sum = 0;
+/// for item in &array {
+///     sum += item.unwrap_or_default();
+/// }
+/// assert_eq!(sum, 4);
+/// ```
 pub struct ArrayIterator<'a, A: Array> {
     array: &'a A,
+    /// Position of the next element returned by `next`.
     pos: usize,
+    /// One past the position of the next element returned by `next_back`.
+    end: usize,
 }
 
 impl<'a, A: Array> Iterator for ArrayIterator<'a, A> {
     type Item = Option<A::RefItem<'a>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos >= self.array.len() {
+        if self.pos >= self.end {
             None
         } else {
             let item = self.array.get(self.pos);
@@ -26,24 +44,85 @@ impl<'a, A: Array> Iterator for ArrayIterator<'a, A> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (
-            self.array.len() - self.pos,
-            Some(self.array.len() - self.pos),
-        )
+        (self.end - self.pos, Some(self.end - self.pos))
+    }
+}
+
+impl<'a, A: Array> DoubleEndedIterator for ArrayIterator<'a, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(self.array.get(self.end))
+        }
     }
 }
 
 impl<'a, A: Array> ArrayIterator<'a, A> {
     /// Create an [`ArrayIterator`] from [`Array`].
     pub fn new(array: &'a A) -> Self {
-        Self { array, pos: 0 }
+        Self {
+            array,
+            pos: 0,
+            end: array.len(),
+        }
     }
 }
 
 impl<'a, A: Array> ExactSizeIterator for ArrayIterator<'a, A> {
     fn len(&self) -> usize {
-        self.array.len() - self.pos
+        self.end - self.pos
     }
 }
 
 unsafe impl<'a, A: Array> TrustedLen for ArrayIterator<'a, A> {}
+
+/// Implements [`IntoIterator`] for references of all arrays.
+///
+/// A blanket implementation over `&A` is not allowed by the orphan rule, so each array type gets
+/// its own implementation.
+macro_rules! impl_array_into_iterator {
+    ([], $({ $Abc:ident, $abc:ident, $AbcArray:ty, $AbcArrayBuilder:ty, $Owned:ty, $Ref:ty }),*) => {
+        $(
+            impl<'a> IntoIterator for &'a $AbcArray {
+                type Item = Option<<$AbcArray as Array>::RefItem<'a>>;
+                type IntoIter = ArrayIterator<'a, $AbcArray>;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    ArrayIterator::new(self)
+                }
+            }
+        )*
+    };
+}
+
+for_all_variants! { impl_array_into_iterator }
+
+#[cfg(test)]
+mod tests {
+    use crate::array::{Array, I32Array};
+
+    #[test]
+    fn test_rev() {
+        let array = I32Array::from_slice(&[Some(1), None, Some(3)]);
+        assert_eq!(
+            array.iter().rev().collect::<Vec<_>>(),
+            vec![Some(3), None, Some(1)]
+        );
+    }
+
+    #[test]
+    fn test_next_and_next_back_meet() {
+        let array = I32Array::from_slice(&[Some(1), Some(2), Some(3), Some(4)]);
+        let mut iter = array.iter();
+        assert_eq!(iter.next(), Some(Some(1)));
+        assert_eq!(iter.next_back(), Some(Some(4)));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(Some(3)));
+        assert_eq!(iter.next(), Some(Some(2)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.len(), 0);
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! JSON array and array builders.
+
+use std::collections::TryReserveError;
+
+use super::{Array, ArrayBuilder, ArrayIterator, PhysicalType, StringArray, StringArrayBuilder};
+use crate::scalar::{InvalidJson, Json, JsonRef};
+
+/// An [`Array`] that stores [`Json`], which is physically a [`StringArray`] of valid JSON texts.
+#[derive(Clone)]
+pub struct JsonArray(StringArray);
+
+impl Array for JsonArray {
+    type Builder = JsonArrayBuilder;
+
+    type OwnedItem = Json;
+
+    type RefItem<'a> = JsonRef<'a>;
+
+    const PHYSICAL_TYPE: PhysicalType = PhysicalType::Json;
+
+    fn get(&self, idx: usize) -> Option<JsonRef<'_>> {
+        self.0.get(idx).map(JsonRef::new_unchecked)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn iter(&self) -> ArrayIterator<Self> {
+        ArrayIterator::new(self)
+    }
+}
+
+impl JsonArray {
+    /// Build an array from JSON texts, returning an error if any of them is not valid.
+    pub fn parse_slice(data: &[Option<&str>]) -> Result<Self, InvalidJson> {
+        let mut builder = JsonArrayBuilder::with_capacity(data.len());
+        for item in data {
+            builder.push(item.map(JsonRef::parse).transpose()?);
+        }
+        Ok(builder.finish())
+    }
+
+    /// Get the JSON texts as a [`StringArray`].
+    pub fn as_string_array(&self) -> &StringArray {
+        &self.0
+    }
+}
+
+/// [`ArrayBuilder`] for [`Json`].
+pub struct JsonArrayBuilder(StringArrayBuilder);
+
+impl ArrayBuilder for JsonArrayBuilder {
+    type Array = JsonArray;
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self(StringArrayBuilder::with_capacity(capacity))
+    }
+
+    fn push(&mut self, value: Option<JsonRef<'_>>) {
+        self.0.push(value.map(|v| v.as_str()));
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.0.try_reserve(additional)
+    }
+
+    fn finish(self) -> Self::Array {
+        JsonArray(self.0.finish())
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use super::*;
+    use crate::array::ArrayImpl;
+    use crate::scalar::ScalarRefImpl;
+
+    #[test]
+    fn test_build_json_array() {
+        let array = JsonArray::parse_slice(&[Some(r#"{"a": 1}"#), None, Some("[]")]).unwrap();
+        assert_eq!(array.get(0).unwrap().as_str(), r#"{"a": 1}"#);
+        assert!(array.get(1).is_none());
+        assert_eq!(
+            array.as_string_array().iter().collect::<Vec<_>>(),
+            vec![Some(r#"{"a": 1}"#), None, Some("[]")]
+        );
+
+        let array: ArrayImpl = array.into();
+        assert_eq!(
+            array.get(2),
+            Some(ScalarRefImpl::Json(JsonRef::parse("[]").unwrap()))
+        );
+
+        assert!(JsonArray::parse_slice(&[Some("{}"), Some("{")]).is_err());
+    }
+}
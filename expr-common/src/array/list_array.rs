@@ -1,9 +1,15 @@
 // Copyright 2022 Alex Chi. Licensed under Apache-2.0.
 
+use std::collections::TryReserveError;
+
+use anyhow::{anyhow, Result};
 use bitvec::prelude::BitVec;
 
-use super::{Array, ArrayBuilder, ArrayBuilderImpl, ArrayIterator, BoxedArray};
+use super::{
+    Array, ArrayBuilder, ArrayBuilderImpl, ArrayImpl, ArrayIterator, BoxedArray, PhysicalType,
+};
 use crate::scalar::{List, ListRef};
+use crate::TypeMismatch;
 
 #[derive(Clone)]
 pub struct ListArray {
@@ -24,6 +30,8 @@ impl Array for ListArray {
 
     type RefItem<'a> = ListRef<'a>;
 
+    const PHYSICAL_TYPE: PhysicalType = PhysicalType::List;
+
     fn get(&self, idx: usize) -> Option<ListRef<'_>> {
         if self.bitmap[idx] {
             Some(ListRef {
@@ -36,7 +44,7 @@ impl Array for ListArray {
     }
 
     fn len(&self) -> usize {
-        self.data.len()
+        self.bitmap.len()
     }
 
     fn iter(&self) -> ArrayIterator<Self> {
@@ -44,6 +52,62 @@ impl Array for ListArray {
     }
 }
 
+impl ListArray {
+    /// Create a list array from the flattened elements of all lists, where the `i`-th list is
+    /// `values[offsets[i]..offsets[i + 1]]`, and is null if the `i`-th bit of `bitmap` is not set.
+    ///
+    /// Returns an error if `offsets` doesn't have one more entry than `bitmap`, is decreasing, or
+    /// doesn't end at the length of `values`.
+    pub fn from_data(values: ArrayImpl, offsets: Vec<usize>, bitmap: BitVec) -> Result<ListArray> {
+        if offsets.len() != bitmap.len() + 1 {
+            return Err(anyhow!(
+                "expect {} offsets for {} lists, get {}",
+                bitmap.len() + 1,
+                bitmap.len(),
+                offsets.len()
+            ));
+        }
+        if let Some(idx) = offsets.windows(2).position(|w| w[0] > w[1]) {
+            return Err(anyhow!(
+                "offsets must not decrease, get {} after {} at {}",
+                offsets[idx + 1],
+                offsets[idx],
+                idx + 1
+            ));
+        }
+        let last = *offsets.last().unwrap();
+        if last != values.len() {
+            return Err(anyhow!(
+                "last offset {} doesn't match the length of values {}",
+                last,
+                values.len()
+            ));
+        }
+        Ok(ListArray {
+            data: values.into_boxed_array(),
+            offsets,
+            bitmap,
+        })
+    }
+
+    /// Flatten all lists into a single array of their elements, which is the inverse of building a
+    /// list array. Also returns the index of the list that each element comes from. Null lists and
+    /// empty lists contribute no elements.
+    pub fn explode(&self) -> (ArrayImpl, Vec<usize>) {
+        let mut builder = self.data.new_builder(self.data.len());
+        let mut parents = Vec::with_capacity(self.data.len());
+        for (idx, list) in self.iter().enumerate() {
+            if let Some(list) = list {
+                for i in list.offset.0..list.offset.1 {
+                    builder.push(list.array.get(i));
+                    parents.push(idx);
+                }
+            }
+        }
+        (builder.finish(), parents)
+    }
+}
+
 pub struct ListArrayBuilder {
     /// The actual data of this array.
     builder: Box<Option<ArrayBuilderImpl>>,
@@ -58,21 +122,10 @@ pub struct ListArrayBuilder {
     number_of_items: usize,
 }
 
-impl ArrayBuilder for ListArrayBuilder {
-    type Array = ListArray;
-
-    fn with_capacity(capacity: usize) -> Self {
-        let mut offsets = Vec::with_capacity(capacity + 1);
-        offsets.push(0);
-        Self {
-            builder: Box::new(None),
-            bitmap: BitVec::with_capacity(capacity),
-            offsets,
-            number_of_items: 0,
-        }
-    }
-
-    fn push(&mut self, value: Option<ListRef<'_>>) {
+impl ListArrayBuilder {
+    /// Append a list. Returns an error if the elements are not of the same physical type as the
+    /// elements of previous lists, in which case nothing is appended.
+    pub fn try_push(&mut self, value: Option<ListRef<'_>>) -> Result<(), TypeMismatch> {
         match value {
             Some(v) => {
                 // Dynamically detect the `ListArray` type upon first push.
@@ -80,6 +133,12 @@ impl ArrayBuilder for ListArrayBuilder {
                     self.builder = Box::new(Some(v.array.new_builder(self.bitmap.capacity())));
                 }
                 let builder = (*self.builder).as_mut().unwrap();
+                if builder.physical_type() != v.array.physical_type() {
+                    return Err(TypeMismatch(
+                        builder.physical_type(),
+                        v.array.physical_type(),
+                    ));
+                }
                 for i in v.offset.0..v.offset.1 {
                     builder.push(v.array.get(i));
                 }
@@ -92,6 +151,42 @@ impl ArrayBuilder for ListArrayBuilder {
                 self.bitmap.push(false);
             }
         }
+        Ok(())
+    }
+}
+
+impl ArrayBuilder for ListArrayBuilder {
+    type Array = ListArray;
+
+    fn with_capacity(capacity: usize) -> Self {
+        let mut offsets = Vec::with_capacity(capacity + 1);
+        offsets.push(0);
+        Self {
+            builder: Box::new(None),
+            bitmap: BitVec::with_capacity(capacity),
+            offsets,
+            number_of_items: 0,
+        }
+    }
+
+    /// Append a list. Panics if the elements are not of the same physical type as the elements of
+    /// previous lists. See [`ListArrayBuilder::try_push`].
+    fn push(&mut self, value: Option<ListRef<'_>>) {
+        if let Err(err) = self.try_push(value) {
+            panic!("failed to push to list array: {}", err);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.bitmap.len()
+    }
+
+    /// Reserve for `additional` lists. Items in the lists are not reserved, as the number of items
+    /// is unknown.
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.offsets.try_reserve(additional)?;
+        self.bitmap.reserve(additional);
+        Ok(())
     }
 
     fn finish(self) -> Self::Array {
@@ -146,4 +241,97 @@ mod tests {
         assert_eq!(array4.get(1), None);
         assert_eq!(array4.get(2), Some(ScalarRefImpl::Int32(2)));
     }
+
+    #[test]
+    fn test_list_element_type_mismatch() {
+        let mut builder = ListArrayBuilder::with_capacity(2);
+        let array1: ArrayImpl = I32Array::from_slice(&[Some(1)]).into();
+        let array1 = array1.into_boxed_array();
+        builder.try_push(Some((&array1).into())).unwrap();
+        let array2: ArrayImpl = StringArray::from_slice(&[Some("a")]).into();
+        let array2 = array2.into_boxed_array();
+        let err = builder.try_push(Some((&array2).into())).unwrap_err();
+        assert_eq!(err.0, PhysicalType::Int32);
+        assert_eq!(err.1, PhysicalType::String);
+        let message = err.to_string();
+        assert!(
+            message.contains("Int32") && message.contains("String"),
+            "{}",
+            message
+        );
+
+        // The failed push leaves the builder untouched.
+        assert_eq!(builder.len(), 1);
+        assert_eq!(builder.finish().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected Int32, get String")]
+    fn test_list_push_type_mismatch() {
+        let mut builder = ListArrayBuilder::with_capacity(2);
+        let array1: ArrayImpl = I32Array::from_slice(&[Some(1)]).into();
+        let array1 = array1.into_boxed_array();
+        builder.push(Some((&array1).into()));
+        let array2: ArrayImpl = StringArray::from_slice(&[Some("a")]).into();
+        let array2 = array2.into_boxed_array();
+        builder.push(Some((&array2).into()));
+    }
+
+    #[test]
+    fn test_list_from_data() {
+        use bitvec::prelude::BitVec;
+
+        let values: ArrayImpl = I32Array::from_slice(&[Some(0), Some(1), None, Some(3)]).into();
+        let bitmap = |bits: &[bool]| bits.iter().copied().collect::<BitVec>();
+
+        let list_array = ListArray::from_data(
+            values.clone(),
+            vec![0, 2, 2, 4],
+            bitmap(&[true, false, true]),
+        )
+        .unwrap();
+        assert_eq!(list_array.len(), 3);
+        let list = list_array.get(0).unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.get(1), Some(ScalarRefImpl::Int32(1)));
+        assert!(list_array.get(1).is_none());
+        let list = list_array.get(2).unwrap();
+        assert_eq!(list.get(0), None);
+        assert_eq!(list.get(1), Some(ScalarRefImpl::Int32(3)));
+
+        // Wrong number of offsets.
+        assert!(ListArray::from_data(values.clone(), vec![0, 4], bitmap(&[true, true])).is_err());
+        // Decreasing offsets.
+        assert!(
+            ListArray::from_data(values.clone(), vec![0, 3, 2, 4], bitmap(&[true; 3])).is_err()
+        );
+        // Last offset doesn't match the values.
+        assert!(ListArray::from_data(values, vec![0, 1, 2, 3], bitmap(&[true; 3])).is_err());
+    }
+
+    #[test]
+    fn test_list_explode() {
+        let mut builder = ListArrayBuilder::with_capacity(0);
+        let array1: ArrayImpl = I32Array::from_slice(&[Some(0), None]).into();
+        let array2: ArrayImpl = I32Array::from_slice(&[]).into();
+        let array3: ArrayImpl = I32Array::from_slice(&[Some(3)]).into();
+        let (array1, array2, array3) = (
+            array1.into_boxed_array(),
+            array2.into_boxed_array(),
+            array3.into_boxed_array(),
+        );
+        builder.push(Some((&array1).into()));
+        builder.push(Some((&array2).into()));
+        builder.push(None);
+        builder.push(Some((&array3).into()));
+        let list_array = builder.finish();
+
+        let (values, parents) = list_array.explode();
+        let values: I32Array = values.try_into().unwrap();
+        assert_eq!(
+            values.iter().collect::<Vec<_>>(),
+            vec![Some(0), None, Some(3)]
+        );
+        assert_eq!(parents, vec![0, 0, 3]);
+    }
 }
@@ -0,0 +1,102 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Null array and array builders.
+//!
+//! [`NullArray`] represents a column whose values are all null, e.g., `SELECT NULL`, where the
+//! type of the column is unknown. It only stores the length.
+
+use std::collections::TryReserveError;
+
+use super::{Array, ArrayBuilder, ArrayIterator, PhysicalType};
+
+/// An [`Array`] of which all elements are null.
+#[derive(Clone)]
+pub struct NullArray {
+    len: usize,
+}
+
+impl Array for NullArray {
+    type Builder = NullArrayBuilder;
+
+    type OwnedItem = ();
+
+    type RefItem<'a> = ();
+
+    const PHYSICAL_TYPE: PhysicalType = PhysicalType::Null;
+
+    fn get(&self, idx: usize) -> Option<()> {
+        assert!(idx < self.len, "index out of bounds");
+        None
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> ArrayIterator<Self> {
+        ArrayIterator::new(self)
+    }
+}
+
+/// [`ArrayBuilder`] for [`NullArray`].
+pub struct NullArrayBuilder {
+    len: usize,
+}
+
+impl ArrayBuilder for NullArrayBuilder {
+    type Array = NullArray;
+
+    fn with_capacity(_capacity: usize) -> Self {
+        Self { len: 0 }
+    }
+
+    /// Append a null. `Some(())` is the only value of the null type, so it is also appended as a
+    /// null.
+    fn push(&mut self, _value: Option<()>) {
+        self.len += 1;
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn try_reserve(&mut self, _additional: usize) -> Result<(), TryReserveError> {
+        Ok(())
+    }
+
+    fn finish(self) -> Self::Array {
+        NullArray { len: self.len }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::{ArrayBuilderImpl, ArrayImpl};
+    use crate::scalar::ScalarRefImpl;
+
+    #[test]
+    fn test_null_array() {
+        let array = NullArray::from_slice(&[None, None, None]);
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.iter().collect::<Vec<_>>(), vec![None, None, None]);
+    }
+
+    #[test]
+    fn test_push_null_value() {
+        let mut builder = NullArrayBuilder::with_capacity(1);
+        builder.push(Some(()));
+        assert_eq!(builder.finish().iter().collect::<Vec<_>>(), vec![None]);
+
+        let mut builder = ArrayBuilderImpl::with_capacity(PhysicalType::Null, 2);
+        builder.try_push(Some(ScalarRefImpl::Null(()))).unwrap();
+        builder.try_push(None).unwrap();
+        let array: NullArray = builder.finish().try_into().unwrap();
+        assert_eq!(array.len(), 2);
+
+        let array: NullArray = ArrayImpl::broadcast(ScalarRefImpl::Null(()), 3)
+            .try_into()
+            .unwrap();
+        assert_eq!(array.iter().collect::<Vec<_>>(), vec![None, None, None]);
+    }
+}
@@ -0,0 +1,860 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Contains type-erased operations over [`ArrayImpl`]
+//!
+//! Operations in this module work on [`ScalarRefImpl`] returned by [`ArrayImpl::get`], so that
+//! they can be applied to any variant of array without monomorphizing on the array type.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use bitvec::slice::BitSlice;
+
+use super::{
+    ArrayBuilder, ArrayBuilderImpl, ArrayImpl, PhysicalType, StringArray, StringArrayBuilder,
+};
+use crate::scalar::{ScalarImpl, ScalarRefImpl, TotalOrd};
+use crate::TypeMismatch;
+
+/// Monotonicity of an array, as returned by [`ArrayImpl::monotonicity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Monotonic {
+    /// Every element is greater than or equal to the previous one.
+    Increasing,
+    /// Every element is less than or equal to the previous one.
+    Decreasing,
+    /// All elements are equal. Empty arrays and single-element arrays are constant.
+    Constant,
+    /// The array is neither non-decreasing nor non-increasing, or contains nulls.
+    None,
+}
+
+impl ArrayImpl {
+    /// Split a sorted array into runs of equal values.
+    ///
+    /// Returns `[start, end)` of each run. Adjacent nulls are grouped together, which is what a
+    /// streaming `GROUP BY` executor expects.
+    pub fn group_ranges(&self) -> Vec<(usize, usize)> {
+        let mut ranges = vec![];
+        let mut start = 0;
+        for idx in 1..self.len() {
+            if self.get(idx) != self.get(start) {
+                ranges.push((start, idx));
+                start = idx;
+            }
+        }
+        if start < self.len() {
+            ranges.push((start, self.len()));
+        }
+        ranges
+    }
+
+    /// Get distinct values of the array in first-seen order. All nulls collapse into one null.
+    ///
+    /// This works on unsorted arrays. Floats are compared with [`TotalOrd`], so all `NaN`s collapse
+    /// into one `NaN`, and `-0.0` collapses into `0.0` if it comes first.
+    pub fn distinct(&self) -> ArrayImpl {
+        let mut seen = HashSet::new();
+        let mut builder = self.new_builder(self.len());
+        for idx in 0..self.len() {
+            let value = self.get(idx);
+            if seen.insert(value) {
+                builder.push(value);
+            }
+        }
+        builder.finish()
+    }
+
+    /// Build a new array from the elements at `indices` with a type-erased builder. Panics if any
+    /// index is out of bounds.
+    pub(crate) fn take_by_builder(&self, indices: &[usize]) -> ArrayImpl {
+        let mut builder = self.new_builder(indices.len());
+        for &idx in indices {
+            assert!(
+                idx < self.len(),
+                "index {} out of bounds for array of length {}",
+                idx,
+                self.len()
+            );
+            builder.push(self.get(idx));
+        }
+        builder.finish()
+    }
+
+    /// Build a new array from the elements at `indices`, where a `None` index produces a null.
+    ///
+    /// This is the gather step of outer joins, where unmatched rows are padded with nulls.
+    /// Returns an error if any index is out of bounds.
+    pub fn take_opt(&self, indices: &[Option<usize>]) -> Result<ArrayImpl> {
+        let mut builder = self.new_builder(indices.len());
+        for &idx in indices {
+            match idx {
+                Some(idx) if idx >= self.len() => {
+                    return Err(anyhow!(
+                        "index {} out of bounds for array of length {}",
+                        idx,
+                        self.len()
+                    ))
+                }
+                Some(idx) => builder.push(self.get(idx)),
+                None => builder.push(None),
+            }
+        }
+        Ok(builder.finish())
+    }
+
+    /// Compare the elements at `i` and `j` in the given order.
+    ///
+    /// Values are compared with [`TotalOrd`], so `NaN` is greater than all other floats.
+    pub(crate) fn compare_at(&self, i: usize, j: usize, desc: bool, nulls_first: bool) -> Ordering {
+        match (self.get(i), self.get(j)) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) if nulls_first => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) if nulls_first => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => {
+                let ord = a.total_cmp(&b);
+                if desc {
+                    ord.reverse()
+                } else {
+                    ord
+                }
+            }
+        }
+    }
+
+    /// Get the permutation that stably sorts the array. Apply it with [`ArrayImpl::take`].
+    pub fn sort_to_indices(&self, desc: bool, nulls_first: bool) -> Vec<usize> {
+        let mut indices = (0..self.len()).collect::<Vec<_>>();
+        indices.sort_by(|&i, &j| self.compare_at(i, j, desc, nulls_first));
+        indices
+    }
+
+    /// Get the permutation that stably sorts the array in ascending order. Apply it with
+    /// [`ArrayImpl::take`].
+    ///
+    /// Nulls are placed before all values if `nulls_first`, and after all values otherwise. Floats
+    /// are totally ordered: `NaN` is greater than all other values.
+    pub fn argsort(&self, nulls_first: bool) -> Vec<usize> {
+        self.sort_to_indices(false, nulls_first)
+    }
+
+    /// Check whether the array is non-decreasing or non-increasing in a single pass.
+    ///
+    /// Any null breaks monotonicity, and so do values that are not comparable (e.g., `NaN`).
+    pub fn monotonicity(&self) -> Monotonic {
+        let (mut increasing, mut decreasing) = (true, true);
+        let mut prev = match self.len() {
+            0 => return Monotonic::Constant,
+            _ => match self.get(0) {
+                Some(value) => value,
+                None => return Monotonic::None,
+            },
+        };
+        for idx in 1..self.len() {
+            let value = match self.get(idx) {
+                Some(value) => value,
+                None => return Monotonic::None,
+            };
+            match prev.partial_cmp(&value) {
+                Some(Ordering::Less) => decreasing = false,
+                Some(Ordering::Greater) => increasing = false,
+                Some(Ordering::Equal) => {}
+                None => return Monotonic::None,
+            }
+            if !increasing && !decreasing {
+                return Monotonic::None;
+            }
+            prev = value;
+        }
+        match (increasing, decreasing) {
+            (true, true) => Monotonic::Constant,
+            (true, false) => Monotonic::Increasing,
+            (false, true) => Monotonic::Decreasing,
+            (false, false) => unreachable!(),
+        }
+    }
+
+    /// Count the non-null elements that satisfy `f`, without materializing the results of `f` as an
+    /// array.
+    pub fn count_where(&self, f: impl Fn(ScalarRefImpl<'_>) -> bool) -> usize {
+        (0..self.len())
+            .filter_map(|idx| self.get(idx))
+            .filter(|value| f(*value))
+            .count()
+    }
+
+    /// Get the minimum and maximum non-null values of the array in a single pass. Returns `None`s
+    /// if all values are null. Values are compared with [`TotalOrd`], so `NaN` is the maximum of
+    /// a float array that contains it.
+    pub fn min_max(&self) -> (Option<ScalarImpl>, Option<ScalarImpl>) {
+        let mut min: Option<ScalarRefImpl> = None;
+        let mut max: Option<ScalarRefImpl> = None;
+        for value in (0..self.len()).filter_map(|idx| self.get(idx)) {
+            if min.map_or(true, |min| value.total_cmp(&min) == Ordering::Less) {
+                min = Some(value);
+            }
+            if max.map_or(true, |max| value.total_cmp(&max) == Ordering::Greater) {
+                max = Some(value);
+            }
+        }
+        (min.map(ScalarImpl::from_ref), max.map(ScalarImpl::from_ref))
+    }
+
+    /// Widen every element to a numeric type. See [`ScalarRefImpl::widen_to`].
+    pub fn widen_to(&self, ty: PhysicalType) -> Result<ArrayImpl> {
+        if self.physical_type() == ty {
+            return Ok(self.clone());
+        }
+        if self.physical_type().common_numeric_type(ty) != Some(ty) {
+            return Err(TypeMismatch(ty, self.physical_type()).into());
+        }
+        let mut builder = ArrayBuilderImpl::with_capacity(ty, self.len());
+        for idx in 0..self.len() {
+            match self.get(idx) {
+                Some(value) => builder.push(Some(value.widen_to(ty)?.as_scalar_ref())),
+                None => builder.push(None),
+            }
+        }
+        Ok(builder.finish())
+    }
+
+    /// Render every element as a string with [`Display`](std::fmt::Display). Nulls stay null.
+    pub fn to_string_array(&self) -> StringArray {
+        let mut builder = StringArrayBuilder::with_capacity(self.len());
+        for idx in 0..self.len() {
+            builder.push(self.get(idx).map(|x| x.to_string()).as_deref());
+        }
+        builder.finish()
+    }
+
+    /// Take the value of `self` if it's not null, otherwise the value of `other`, at each
+    /// position. Both arrays must be of the same physical type and length.
+    ///
+    /// `other` is only read at positions where `self` is null.
+    pub fn coalesce_with(&self, other: &ArrayImpl) -> Result<ArrayImpl> {
+        if self.physical_type() != other.physical_type() {
+            return Err(TypeMismatch(self.physical_type(), other.physical_type()).into());
+        }
+        if self.len() != other.len() {
+            return Err(anyhow!(
+                "array length mismatch: {} and {}",
+                self.len(),
+                other.len()
+            ));
+        }
+        Ok(self.fill_nulls_with(|idx| other.get(idx)))
+    }
+
+    /// Concatenate arrays of the same physical type into one array, e.g., when merging batches.
+    ///
+    /// Returns an error if `arrays` is empty, as the type of the result is unknown.
+    pub fn concat(arrays: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        let (first, rest) = arrays
+            .split_first()
+            .ok_or_else(|| anyhow!("expect at least 1 array to concat"))?;
+        if rest.is_empty() {
+            return Ok((*first).clone());
+        }
+        let ty = first.physical_type();
+        if let Some(other) = rest.iter().find(|a| a.physical_type() != ty) {
+            return Err(TypeMismatch(ty, other.physical_type()).into());
+        }
+        let capacity = arrays.iter().map(|a| a.len()).sum();
+        let mut builder = ArrayBuilderImpl::with_capacity(ty, capacity);
+        for array in arrays {
+            for idx in 0..array.len() {
+                builder.push(array.get(idx));
+            }
+        }
+        Ok(builder.finish())
+    }
+
+    /// Replace each null at position `idx` with `f(idx)`. `f` is only called at null positions.
+    ///
+    /// The values returned by `f` must be of the same physical type as `self`.
+    pub fn fill_nulls_with<'a>(&self, f: impl Fn(usize) -> Option<ScalarRefImpl<'a>>) -> ArrayImpl {
+        let mut builder = self.new_builder(self.len());
+        for idx in 0..self.len() {
+            builder.push(self.get(idx).or_else(|| f(idx)));
+        }
+        builder.finish()
+    }
+
+    /// Export the validity of the array as a packed bitmap, where the `i`-th bit (LSB-first) is
+    /// set if the `i`-th element is not null. This is the same layout as Arrow's validity buffer.
+    ///
+    /// If the length of the array is not a multiple of 8, the last byte is padded with zeros.
+    pub fn validity_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; (self.len() + 7) / 8];
+        for idx in 0..self.len() {
+            if self.get(idx).is_some() {
+                bytes[idx / 8] |= 1 << (idx % 8);
+            }
+        }
+        bytes
+    }
+
+    /// Iterate over `[start, end)` of each run of consecutive nulls.
+    ///
+    /// The runs are located with [`BitSlice::first_zero`] and [`BitSlice::first_one`], which scan
+    /// a word at a time, so long runs of non-nulls or nulls are cheap to skip. Primitive arrays
+    /// and string arrays are scanned over their null bitmaps in place. For other arrays, the
+    /// validity is collected into a bitmap first.
+    pub fn null_runs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let validity: Cow<'_, BitSlice> = match self.null_bitmap() {
+            Some(bitmap) => Cow::Borrowed(bitmap.as_bitslice()),
+            None => Cow::Owned((0..self.len()).map(|idx| self.get(idx).is_some()).collect()),
+        };
+        let mut pos = 0;
+        std::iter::from_fn(move || {
+            let start = pos + validity[pos..].first_zero()?;
+            let end = validity[start..]
+                .first_one()
+                .map_or(validity.len(), |offset| start + offset);
+            pos = end;
+            Some((start, end))
+        })
+    }
+
+    /// Combine the validity of the array with `mask`: an element is null if it's null in the array
+    /// or its bit in `mask` is not set. This propagates nulls of a parent (e.g., a struct) to its
+    /// children.
+    pub fn mask_combine(&self, mask: &BitSlice) -> ArrayImpl {
+        assert_eq!(self.len(), mask.len(), "mask length mismatch");
+        let mut builder = self.new_builder(self.len());
+        for idx in 0..self.len() {
+            builder.push(self.get(idx).filter(|_| mask[idx]));
+        }
+        builder.finish()
+    }
+
+    /// Apply a packed bitmap in the layout of [`ArrayImpl::validity_bytes`] to the array.
+    ///
+    /// Elements whose bit is not set become null, and existing nulls are kept. Returns an error
+    /// if the bitmap doesn't have exactly the bytes needed for the array.
+    pub fn with_validity_bytes(&self, bytes: &[u8]) -> Result<ArrayImpl> {
+        if bytes.len() != (self.len() + 7) / 8 {
+            return Err(anyhow!(
+                "expect {} bytes of validity for {} elements, get {}",
+                (self.len() + 7) / 8,
+                self.len(),
+                bytes.len()
+            ));
+        }
+        let mut builder = self.new_builder(self.len());
+        for idx in 0..self.len() {
+            if bytes[idx / 8] & (1 << (idx % 8)) != 0 {
+                builder.push(self.get(idx));
+            } else {
+                builder.push(None);
+            }
+        }
+        Ok(builder.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::*;
+    use crate::scalar::{ListRef, ScalarImpl, ScalarRefImpl};
+
+    #[test]
+    fn test_group_ranges() {
+        let array: ArrayImpl =
+            I32Array::from_slice(&[Some(1), Some(1), Some(2), Some(3), Some(3), Some(3)]).into();
+        assert_eq!(array.group_ranges(), vec![(0, 2), (2, 3), (3, 6)]);
+
+        let array: ArrayImpl = StringArray::from_slice(&[None, None, Some("a"), None]).into();
+        assert_eq!(array.group_ranges(), vec![(0, 2), (2, 3), (3, 4)]);
+
+        let array: ArrayImpl = I32Array::from_slice(&[]).into();
+        assert!(array.group_ranges().is_empty());
+    }
+
+    #[test]
+    fn test_distinct() {
+        let array: ArrayImpl =
+            I32Array::from_slice(&[Some(1), Some(2), Some(1), None, Some(2), None]).into();
+        let array: I32Array = array.distinct().try_into().unwrap();
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some(1), Some(2), None]
+        );
+
+        let array: ArrayImpl =
+            StringArray::from_slice(&[Some("b"), Some("a"), Some("b"), Some("")]).into();
+        let array: StringArray = array.distinct().try_into().unwrap();
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some("b"), Some("a"), Some("")]
+        );
+
+        let array: ArrayImpl = F64Array::from_slice(&[
+            Some(f64::NAN),
+            Some(1.0),
+            Some(-f64::NAN),
+            Some(2.0),
+            Some(0.0),
+            Some(-0.0),
+        ])
+        .into();
+        let array: F64Array = array.distinct().try_into().unwrap();
+        let values = array.iter().map(Option::unwrap).collect::<Vec<_>>();
+        assert_eq!(values.len(), 4);
+        assert!(values[0].is_nan());
+        assert_eq!(&values[1..], &[1.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_sort_to_indices() {
+        let array: ArrayImpl = I32Array::from_slice(&[Some(2), None, Some(1), Some(2)]).into();
+        assert_eq!(array.sort_to_indices(false, false), vec![2, 0, 3, 1]);
+        assert_eq!(array.sort_to_indices(false, true), vec![1, 2, 0, 3]);
+        assert_eq!(array.sort_to_indices(true, false), vec![0, 3, 2, 1]);
+
+        let sorted: I32Array = array
+            .take(&array.sort_to_indices(true, true))
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            sorted.iter().collect::<Vec<_>>(),
+            vec![None, Some(2), Some(2), Some(1)]
+        );
+
+        let array: ArrayImpl =
+            F64Array::from_slice(&[Some(1.0), Some(f64::NAN), None, Some(f64::NAN), Some(0.0)])
+                .into();
+        assert_eq!(array.sort_to_indices(false, false), vec![4, 0, 1, 3, 2]);
+        assert_eq!(array.sort_to_indices(true, true), vec![2, 1, 3, 0, 4]);
+    }
+
+    #[test]
+    fn test_argsort() {
+        let array: ArrayImpl = I32Array::from_slice(&[Some(3), None, Some(1), Some(2)]).into();
+        assert_eq!(array.argsort(false), vec![2, 3, 0, 1]);
+        assert_eq!(array.argsort(true), vec![1, 2, 3, 0]);
+
+        let sorted: I32Array = array.take(&array.argsort(true)).try_into().unwrap();
+        assert_eq!(
+            sorted.iter().collect::<Vec<_>>(),
+            vec![None, Some(1), Some(2), Some(3)]
+        );
+
+        let array: ArrayImpl =
+            F64Array::from_slice(&[Some(f64::NAN), None, Some(1.0), Some(-1.0)]).into();
+        assert_eq!(array.argsort(false), vec![3, 2, 0, 1]);
+    }
+
+    #[test]
+    fn test_monotonicity() {
+        let monotonicity = |values: &[Option<i32>]| -> Monotonic {
+            ArrayImpl::from(I32Array::from_slice(values)).monotonicity()
+        };
+        assert_eq!(
+            monotonicity(&[Some(1), Some(2), Some(2), Some(3)]),
+            Monotonic::Increasing
+        );
+        assert_eq!(
+            monotonicity(&[Some(3), Some(3), Some(2), Some(1)]),
+            Monotonic::Decreasing
+        );
+        assert_eq!(
+            monotonicity(&[Some(2), Some(2), Some(2)]),
+            Monotonic::Constant
+        );
+        assert_eq!(monotonicity(&[]), Monotonic::Constant);
+        assert_eq!(monotonicity(&[Some(1), Some(3), Some(2)]), Monotonic::None);
+        assert_eq!(monotonicity(&[Some(1), None, Some(2)]), Monotonic::None);
+    }
+
+    #[test]
+    fn test_take_opt() {
+        let array: ArrayImpl = StringArray::from_slice(&[Some("a"), None, Some("c")]).into();
+        let taken: StringArray = array
+            .take_opt(&[Some(0), None, Some(2), Some(1)])
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            taken.iter().collect::<Vec<_>>(),
+            vec![Some("a"), None, Some("c"), None]
+        );
+        assert!(array.take_opt(&[Some(3)]).is_err());
+    }
+
+    #[test]
+    fn test_fill_forward_backward() {
+        let array: ArrayImpl = I32Array::from_slice(&[Some(1), None, None, Some(4), None]).into();
+        let filled: I32Array = array.fill_forward().try_into().unwrap();
+        assert_eq!(
+            filled.iter().collect::<Vec<_>>(),
+            vec![Some(1), Some(1), Some(1), Some(4), Some(4)]
+        );
+        let filled: I32Array = array.fill_backward().try_into().unwrap();
+        assert_eq!(
+            filled.iter().collect::<Vec<_>>(),
+            vec![Some(1), Some(4), Some(4), Some(4), None]
+        );
+
+        let array: ArrayImpl = StringArray::from_slice(&[None, Some("a"), None]).into();
+        let filled: StringArray = array.fill_forward().try_into().unwrap();
+        assert_eq!(
+            filled.iter().collect::<Vec<_>>(),
+            vec![None, Some("a"), Some("a")]
+        );
+    }
+
+    #[test]
+    fn test_null_runs() {
+        let array: ArrayImpl = I32Array::from_slice(&[
+            Some(1),
+            None,
+            None,
+            Some(4),
+            Some(5),
+            None,
+            None,
+            None,
+            Some(9),
+        ])
+        .into();
+        assert_eq!(array.null_runs().collect::<Vec<_>>(), vec![(1, 3), (5, 8)]);
+
+        let array: ArrayImpl = StringArray::from_slice(&[None, Some("a"), None]).into();
+        assert_eq!(array.null_runs().collect::<Vec<_>>(), vec![(0, 1), (2, 3)]);
+
+        let mut values = vec![Some(0); 100];
+        values[70..].fill(None);
+        let array: ArrayImpl = I32Array::from_slice(&values).into();
+        assert_eq!(array.null_runs().collect::<Vec<_>>(), vec![(70, 100)]);
+
+        let array: ArrayImpl = I32Array::from_slice(&[Some(1)]).into();
+        assert_eq!(array.null_runs().next(), None);
+
+        let items: ArrayImpl = I32Array::from_slice(&[Some(1)]).into();
+        let items = items.into_boxed_array();
+        let array: ArrayImpl = ListArray::from_slice(&[Some((&items).into()), None, None]).into();
+        assert_eq!(array.null_runs().collect::<Vec<_>>(), vec![(1, 3)]);
+    }
+
+    #[test]
+    fn test_slice() {
+        let array: ArrayImpl = I32Array::from_slice(&[Some(1), None, Some(3)]).into();
+        let sliced: I32Array = array.slice(1..).try_into().unwrap();
+        assert_eq!(sliced.iter().collect::<Vec<_>>(), vec![None, Some(3)]);
+
+        let array: ArrayImpl = BytesArray::from_slice(&[Some(b"a"), None, Some(b"c")]).into();
+        let sliced: BytesArray = array.slice(1..).try_into().unwrap();
+        assert_eq!(
+            sliced.iter().collect::<Vec<_>>(),
+            vec![None, Some(&b"c"[..])]
+        );
+    }
+
+    #[test]
+    fn test_filter() {
+        let mask = BoolArray::from_slice(&[Some(true), Some(false), None, Some(true)]);
+
+        let array: ArrayImpl = I32Array::from_slice(&[Some(1), Some(2), Some(3), None]).into();
+        let filtered: I32Array = array.filter(&mask).try_into().unwrap();
+        assert_eq!(filtered.iter().collect::<Vec<_>>(), vec![Some(1), None]);
+
+        let array: ArrayImpl =
+            StringArray::from_slice(&[None, Some("b"), Some("c"), Some("d")]).into();
+        let filtered: StringArray = array.filter(&mask).try_into().unwrap();
+        assert_eq!(filtered.iter().collect::<Vec<_>>(), vec![None, Some("d")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "mask length mismatch")]
+    fn test_filter_length_mismatch() {
+        let array = I32Array::from_slice(&[Some(1), Some(2)]);
+        array.filter(&BoolArray::from_slice(&[Some(true)]));
+    }
+
+    #[test]
+    fn test_to_string_array() {
+        let array: ArrayImpl = I32Array::from_slice(&[Some(1), None, Some(-3)]).into();
+        assert_eq!(
+            array.to_string_array().iter().collect::<Vec<_>>(),
+            vec![Some("1"), None, Some("-3")]
+        );
+
+        let array: ArrayImpl = BoolArray::from_slice(&[Some(true), Some(false), None]).into();
+        assert_eq!(
+            array.to_string_array().iter().collect::<Vec<_>>(),
+            vec![Some("true"), Some("false"), None]
+        );
+
+        let items: ArrayImpl = I32Array::from_slice(&[Some(1), Some(2), None]).into();
+        let items = items.into_boxed_array();
+        let mut builder = ListArrayBuilder::with_capacity(3);
+        builder.push(Some((&items).into()));
+        builder.push(None);
+        builder.push(Some(ListRef::from(&items).slice(..1)));
+        let array: ArrayImpl = builder.finish().into();
+        assert_eq!(
+            array.to_string_array().iter().collect::<Vec<_>>(),
+            vec![Some("[1, 2, NULL]"), None, Some("[1]")]
+        );
+    }
+
+    #[test]
+    fn test_count_where() {
+        let array: ArrayImpl =
+            I32Array::from_slice(&[Some(5), Some(11), None, Some(20), Some(10)]).into();
+        assert_eq!(array.count_where(|x| x > ScalarRefImpl::Int32(10)), 2);
+        assert_eq!(array.count_where(|_| true), 4);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let array: ArrayImpl =
+            I32Array::from_slice(&[None, Some(3), Some(-1), None, Some(7)]).into();
+        assert_eq!(
+            array.min_max(),
+            (Some(ScalarImpl::Int32(-1)), Some(ScalarImpl::Int32(7)))
+        );
+
+        let array: ArrayImpl = StringArray::from_slice(&[Some("b"), Some("abc"), Some("c")]).into();
+        assert_eq!(
+            array.min_max(),
+            (
+                Some(ScalarImpl::String("abc".to_string())),
+                Some(ScalarImpl::String("c".to_string()))
+            )
+        );
+
+        let array: ArrayImpl = I32Array::from_slice(&[None, None]).into();
+        assert_eq!(array.min_max(), (None, None));
+
+        // `NaN` is the greatest float, no matter where it appears.
+        for values in [
+            [Some(f64::NAN), Some(1.0), Some(f64::NAN), Some(2.0)],
+            [Some(1.0), Some(f64::NAN), None, Some(2.0)],
+        ] {
+            let array: ArrayImpl = F64Array::from_slice(&values).into();
+            assert_eq!(
+                array.min_max(),
+                (
+                    Some(ScalarImpl::Float64(1.0)),
+                    Some(ScalarImpl::Float64(f64::NAN))
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_widen_to() {
+        let array: ArrayImpl = I16Array::from_slice(&[Some(1), None]).into();
+        let widened: I64Array = array
+            .widen_to(PhysicalType::Int64)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(widened.iter().collect::<Vec<_>>(), vec![Some(1), None]);
+
+        let array: ArrayImpl = I64Array::from_slice(&[None]).into();
+        assert!(array.widen_to(PhysicalType::Int32).is_err());
+    }
+
+    #[test]
+    fn test_get_owned_many() {
+        let values = {
+            let array: ArrayImpl =
+                StringArray::from_slice(&[Some("a"), None, Some("c"), Some("d")]).into();
+            array.get_owned_many(&[3, 1, 0, 3])
+        };
+        assert_eq!(
+            values,
+            vec![
+                Some(ScalarImpl::String("d".to_string())),
+                None,
+                Some(ScalarImpl::String("a".to_string())),
+                Some(ScalarImpl::String("d".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validity_bytes() {
+        let data = [
+            Some(0),
+            None,
+            Some(2),
+            Some(3),
+            None,
+            None,
+            Some(6),
+            Some(7),
+            None,
+            Some(9),
+        ];
+        let array: ArrayImpl = I32Array::from_slice(&data).into();
+        let bytes = array.validity_bytes();
+        assert_eq!(bytes, vec![0b1100_1101, 0b0000_0010]);
+
+        let all_valid: ArrayImpl = I32Array::from_slice(&[Some(0); 10]).into();
+        let applied: I32Array = all_valid
+            .with_validity_bytes(&bytes)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let expected = data.map(|x| x.map(|_| 0));
+        assert_eq!(applied.iter().collect::<Vec<_>>(), expected);
+        assert_eq!(ArrayImpl::from(applied).validity_bytes(), bytes);
+
+        assert!(array.with_validity_bytes(&[0xff]).is_err());
+    }
+
+    #[test]
+    fn test_coalesce_with() {
+        let a: ArrayImpl = I32Array::from_slice(&[Some(1), None, Some(3), None]).into();
+        let b: ArrayImpl = I32Array::from_slice(&[None, Some(2), Some(0), None]).into();
+        let result: I32Array = a.coalesce_with(&b).unwrap().try_into().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(1), Some(2), Some(3), None]
+        );
+
+        let c: ArrayImpl = StringArray::from_slice(&[None, None, None, None]).into();
+        assert!(a.coalesce_with(&c).is_err());
+        let d: ArrayImpl = I32Array::from_slice(&[None]).into();
+        assert!(a.coalesce_with(&d).is_err());
+    }
+
+    #[test]
+    fn test_push_from() {
+        let src: ArrayImpl =
+            StringArray::from_slice(&[Some("a"), None, Some("c"), Some("d")]).into();
+        let mut builder = ArrayBuilderImpl::with_capacity(PhysicalType::String, 3);
+        for idx in [3, 1, 0] {
+            builder.push_from(&src, idx).unwrap();
+        }
+        let result = builder.finish();
+        for (i, idx) in [3, 1, 0].into_iter().enumerate() {
+            assert_eq!(result.get(i), src.get(idx));
+        }
+
+        let other: ArrayImpl = I32Array::from_slice(&[Some(1)]).into();
+        let mut builder = ArrayBuilderImpl::with_capacity(PhysicalType::String, 1);
+        assert!(builder.push_from(&other, 0).is_err());
+    }
+
+    #[test]
+    fn test_concat() {
+        let a: ArrayImpl = StringArray::from_slice(&[Some("a"), None]).into();
+        let b: ArrayImpl = StringArray::from_slice(&[]).into();
+        let c: ArrayImpl = StringArray::from_slice(&[None, Some("b"), Some("c")]).into();
+        let result: StringArray = ArrayImpl::concat(&[&a, &b, &c])
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some("a"), None, None, Some("b"), Some("c")]
+        );
+
+        let result: StringArray = ArrayImpl::concat(&[&c]).unwrap().try_into().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![None, Some("b"), Some("c")]
+        );
+
+        assert!(ArrayImpl::concat(&[]).is_err());
+
+        let d: ArrayImpl = I32Array::from_slice(&[Some(1)]).into();
+        let err = ArrayImpl::concat(&[&a, &d]).unwrap_err();
+        assert!(err.downcast_ref::<crate::TypeMismatch>().is_some());
+    }
+
+    #[test]
+    fn test_array_impl_eq() {
+        let a: ArrayImpl = I32Array::from_slice(&[Some(1), None]).into();
+        assert_eq!(a, I32Array::from_slice(&[Some(1), None]).into());
+        assert_ne!(a, I32Array::from_slice(&[None, Some(1)]).into());
+        assert_ne!(a, I64Array::from_slice(&[Some(1), None]).into());
+
+        let inner: ArrayImpl = I32Array::from_slice(&[Some(1), None]).into();
+        let inner = inner.into_boxed_array();
+        let list: ArrayImpl = ListArray::from_slice(&[Some((&inner).into()), None]).into();
+        assert_eq!(list, list.clone());
+        assert_ne!(
+            list,
+            ListArray::from_slice(&[None, Some((&inner).into())]).into()
+        );
+    }
+
+    #[test]
+    fn test_builder_len() {
+        let mut builder = ArrayBuilderImpl::with_capacity(PhysicalType::String, 0);
+        assert!(builder.is_empty());
+        builder.push(Some(ScalarRefImpl::String("a")));
+        builder.push(None);
+        builder.push(Some(ScalarRefImpl::String("")));
+        assert_eq!(builder.len(), 3);
+        assert!(!builder.is_empty());
+        assert_eq!(builder.finish().len(), 3);
+
+        let mut builder = ArrayBuilderImpl::with_capacity(PhysicalType::Null, 0);
+        builder.push(None);
+        assert_eq!(builder.len(), 1);
+    }
+
+    #[test]
+    fn test_builder_try_push() {
+        let mut builder = ArrayBuilderImpl::with_capacity(PhysicalType::String, 0);
+        assert!(matches!(
+            builder.try_push(Some(ScalarRefImpl::Int32(1))),
+            Err(crate::TypeMismatch(
+                PhysicalType::String,
+                PhysicalType::Int32
+            ))
+        ));
+        assert!(builder.is_empty());
+        builder.try_push(Some(ScalarRefImpl::String("a"))).unwrap();
+        builder.try_push(None).unwrap();
+        assert_eq!(builder.len(), 2);
+
+        assert!(matches!(
+            builder.try_push_owned(Some(ScalarImpl::Int32(1))),
+            Err(crate::TypeMismatch(
+                PhysicalType::String,
+                PhysicalType::Int32
+            ))
+        ));
+        builder
+            .try_push_owned(Some(ScalarImpl::String("b".to_string())))
+            .unwrap();
+        assert_eq!(builder.len(), 3);
+
+        assert!(matches!(
+            builder.try_append_n(2, Some(ScalarRefImpl::Int32(1))),
+            Err(crate::TypeMismatch(
+                PhysicalType::String,
+                PhysicalType::Int32
+            ))
+        ));
+        builder.append_n(2, Some(ScalarRefImpl::String("c")));
+        builder.append_n(1, None);
+        let array: StringArray = builder.finish().try_into().unwrap();
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some("a"), None, Some("b"), Some("c"), Some("c"), None]
+        );
+    }
+
+    #[test]
+    fn test_builder_push_owned() {
+        let mut builder = ArrayBuilderImpl::with_capacity(PhysicalType::Int32, 0);
+        builder.push_owned(Some(ScalarImpl::Int32(1)));
+        builder.push_owned(None);
+        let array = builder.finish();
+        assert_eq!(array.get(0), Some(ScalarRefImpl::Int32(1)));
+        assert_eq!(array.get(1), None);
+    }
+}
@@ -1,5 +1,13 @@
 // Copyright 2022 Alex Chi. Licensed under Apache-2.0.
 
+use std::fmt::Display;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::datatype::DataType;
+use crate::macros::for_all_variants;
+
 #[derive(Clone, Debug, Copy, Eq, PartialEq)]
 pub enum PhysicalType {
     Int16,
@@ -9,6 +17,114 @@ pub enum PhysicalType {
     Float64,
     Bool,
     String,
+    Bytes,
     Decimal,
+    Date32,
+    Timestamp,
     List,
+    Json,
+    Null,
+    #[cfg(feature = "uuid")]
+    Uuid,
+}
+
+impl PhysicalType {
+    /// Get the logical type that is usually stored as this physical type, e.g., `VARCHAR` for
+    /// `String`, which is an inverse of [`DataType::physical_type`]. Decimals default to the max
+    /// precision with scale 0. Returns `None` if there's no logical type for this physical type,
+    /// i.e., `List` and `Null`.
+    pub fn default_data_type(&self) -> Option<DataType> {
+        let datatype = match self {
+            Self::Int16 => DataType::SmallInt,
+            Self::Int32 => DataType::Integer,
+            Self::Int64 => DataType::BigInt,
+            Self::Float32 => DataType::Real,
+            Self::Float64 => DataType::Double,
+            Self::Bool => DataType::Boolean,
+            Self::String => DataType::Varchar,
+            Self::Bytes => DataType::Bytea,
+            Self::Decimal => DataType::Decimal {
+                scale: 0,
+                precision: 28,
+            },
+            Self::Date32 => DataType::Date,
+            Self::Timestamp => DataType::Timestamp,
+            Self::Json => DataType::Json,
+            #[cfg(feature = "uuid")]
+            Self::Uuid => DataType::Uuid,
+            Self::List | Self::Null => return None,
+        };
+        Some(datatype)
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("Unknown physical type: {0}")]
+pub struct ParsePhysicalTypeError(pub String);
+
+/// Implements [`Display`] and [`FromStr`] for [`PhysicalType`]. The name of each physical type is
+/// the same as its variant name, e.g., `Int32`.
+macro_rules! impl_physical_type_display {
+    ([], $({ $Abc:ident, $abc:ident, $AbcArray:ty, $AbcArrayBuilder:ty, $Owned:ty, $Ref:ty }),*) => {
+        impl Display for PhysicalType {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(
+                        Self::$Abc => write!(f, stringify!($Abc)),
+                    )*
+                }
+            }
+        }
+
+        impl FromStr for PhysicalType {
+            type Err = ParsePhysicalTypeError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $(
+                        stringify!($Abc) => Ok(Self::$Abc),
+                    )*
+                    other => Err(ParsePhysicalTypeError(other.to_string())),
+                }
+            }
+        }
+    };
+}
+
+for_all_variants! { impl_physical_type_display }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! all_physical_types {
+        ([], $({ $Abc:ident, $abc:ident, $AbcArray:ty, $AbcArrayBuilder:ty, $Owned:ty, $Ref:ty }),*) => {
+            [$(PhysicalType::$Abc),*]
+        };
+    }
+
+    #[test]
+    fn test_physical_type_display_from_str() {
+        for pt in for_all_variants! { all_physical_types } {
+            assert_eq!(PhysicalType::from_str(&pt.to_string()).unwrap(), pt);
+        }
+        assert_eq!(PhysicalType::Int32.to_string(), "Int32");
+        assert_eq!(PhysicalType::String.to_string(), "String");
+        assert_eq!(PhysicalType::List.to_string(), "List");
+        assert!(PhysicalType::from_str("int32").is_err());
+    }
+
+    #[test]
+    fn test_default_data_type() {
+        for pt in for_all_variants! { all_physical_types } {
+            match pt.default_data_type() {
+                Some(datatype) => assert_eq!(datatype.physical_type(), pt),
+                None => assert!(matches!(pt, PhysicalType::List | PhysicalType::Null)),
+            }
+        }
+        assert!(matches!(
+            PhysicalType::String.default_data_type(),
+            Some(DataType::Varchar)
+        ));
+    }
 }
@@ -4,15 +4,24 @@
 //!
 //! This module implements array for primitive types, like `i32` and `f32`.
 
+use std::collections::TryReserveError;
+use std::ops::RangeBounds;
+
+use anyhow::{anyhow, Result};
 use bitvec::prelude::BitVec;
 use rust_decimal::Decimal;
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
 
-use super::{Array, ArrayBuilder, ArrayImpl, ArrayIterator};
-use crate::scalar::{Scalar, ScalarRef};
+use super::{resolve_range, Array, ArrayBuilder, ArrayImpl, ArrayIterator, PhysicalType};
+use crate::scalar::{CheckedArith, Date32, Scalar, ScalarRef, Timestamp};
 use crate::TypeMismatch;
 
 /// A type that is primitive, such as `i32` and `i64`.
-pub trait PrimitiveType: Scalar + Default {}
+pub trait PrimitiveType: Scalar {
+    /// The value stored in the data buffer at null positions.
+    fn placeholder() -> Self;
+}
 
 pub type I16Array = PrimitiveArray<i16>;
 pub type I32Array = PrimitiveArray<i32>;
@@ -21,6 +30,10 @@ pub type F32Array = PrimitiveArray<f32>;
 pub type F64Array = PrimitiveArray<f64>;
 pub type BoolArray = PrimitiveArray<bool>;
 pub type DecimalArray = PrimitiveArray<Decimal>;
+pub type DateArray = PrimitiveArray<Date32>;
+pub type TimestampArray = PrimitiveArray<Timestamp>;
+#[cfg(feature = "uuid")]
+pub type UuidArray = PrimitiveArray<Uuid>;
 
 pub type I16ArrayBuilder = PrimitiveArrayBuilder<i16>;
 pub type I32ArrayBuilder = PrimitiveArrayBuilder<i32>;
@@ -29,14 +42,97 @@ pub type F32ArrayBuilder = PrimitiveArrayBuilder<f32>;
 pub type F64ArrayBuilder = PrimitiveArrayBuilder<f64>;
 pub type BoolArrayBuilder = PrimitiveArrayBuilder<bool>;
 pub type DecimalArrayBuilder = PrimitiveArrayBuilder<Decimal>;
+pub type DateArrayBuilder = PrimitiveArrayBuilder<Date32>;
+pub type TimestampArrayBuilder = PrimitiveArrayBuilder<Timestamp>;
+#[cfg(feature = "uuid")]
+pub type UuidArrayBuilder = PrimitiveArrayBuilder<Uuid>;
+
+/// Implements [`PrimitiveType`] for types whose placeholder is [`Default::default`].
+macro_rules! impl_primitive_type_default {
+    ($($t:ty),*) => {
+        $(
+            impl PrimitiveType for $t {
+                fn placeholder() -> Self {
+                    Self::default()
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_type_default! { i16, i32, i64, f32, f64, bool, Decimal, Date32, Timestamp }
+
+/// `Uuid` has no `Default` implementation, so we use the nil UUID as placeholder.
+#[cfg(feature = "uuid")]
+impl PrimitiveType for Uuid {
+    fn placeholder() -> Self {
+        Uuid::nil()
+    }
+}
+
+/// A [`PrimitiveType`] that maps to an order-preserving unsigned key, so that sorting the keys
+/// sorts the values. This enables radix sort over [`PrimitiveArray::to_sort_keys`].
+///
+/// Keys of 64-bit types use the full range of `u64`, so there's no key left for nulls.
+/// [`PrimitiveArray::to_sort_keys`] widens the keys to `u128` to place nulls.
+pub trait SortKey: PrimitiveType {
+    /// Get the order-preserving key of the value.
+    fn sort_key(self) -> u64;
+}
+
+/// Implements [`SortKey`] for signed integers by flipping the sign bit.
+macro_rules! impl_int_sort_key {
+    ($({ $t:ty, $unsigned:ty }),*) => {
+        $(
+            impl SortKey for $t {
+                fn sort_key(self) -> u64 {
+                    ((self as $unsigned) ^ (1 << (<$unsigned>::BITS - 1))) as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_int_sort_key! { { i16, u16 }, { i32, u32 }, { i64, u64 } }
+
+/// Implements [`SortKey`] for floats. Negative floats have all bits flipped, and positive floats
+/// have the sign bit flipped. To follow [`TotalOrd`](crate::scalar::TotalOrd), `-0.0` has the
+/// same key as `0.0`, and all `NaN`s have the same key, which is greater than that of infinity.
+macro_rules! impl_float_sort_key {
+    ($({ $t:ty, $unsigned:ty }),*) => {
+        $(
+            impl SortKey for $t {
+                fn sort_key(self) -> u64 {
+                    let value = if self.is_nan() { <$t>::NAN } else { self + 0.0 };
+                    let bits = value.to_bits();
+                    let sign: $unsigned = 1 << (<$unsigned>::BITS - 1);
+                    let key = if bits & sign != 0 { !bits } else { bits | sign };
+                    key as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_float_sort_key! { { f32, u32 }, { f64, u64 } }
+
+impl SortKey for bool {
+    fn sort_key(self) -> u64 {
+        self as u64
+    }
+}
 
-impl PrimitiveType for i16 {}
-impl PrimitiveType for i32 {}
-impl PrimitiveType for i64 {}
-impl PrimitiveType for f32 {}
-impl PrimitiveType for f64 {}
-impl PrimitiveType for bool {}
-impl PrimitiveType for Decimal {}
+impl SortKey for Date32 {
+    fn sort_key(self) -> u64 {
+        self.0.sort_key()
+    }
+}
+
+impl SortKey for Timestamp {
+    fn sort_key(self) -> u64 {
+        self.0.sort_key()
+    }
+}
 
 /// An [`Array`] that stores [`PrimitiveType`] items.
 ///
@@ -79,6 +175,8 @@ where
     /// calling `get`.
     type RefItem<'a> = T;
 
+    const PHYSICAL_TYPE: PhysicalType = T::PHYSICAL_TYPE;
+
     fn get(&self, idx: usize) -> Option<T> {
         if self.bitmap[idx] {
             Some(self.data[idx])
@@ -87,15 +185,236 @@ where
         }
     }
 
+    unsafe fn get_unchecked(&self, idx: usize) -> Option<T> {
+        if *self.bitmap.get_unchecked(idx) {
+            Some(*self.data.get_unchecked(idx))
+        } else {
+            None
+        }
+    }
+
     fn len(&self) -> usize {
         self.data.len()
     }
 
+    fn is_null(&self, idx: usize) -> bool {
+        !self.bitmap[idx]
+    }
+
+    fn null_count(&self) -> usize {
+        self.bitmap.count_zeros()
+    }
+
     fn iter(&self) -> ArrayIterator<Self> {
         ArrayIterator::new(self)
     }
 }
 
+impl<T: PrimitiveType> FromIterator<Option<T>> for PrimitiveArray<T>
+where
+    Self: Array<RefItem<'static> = T>,
+{
+    fn from_iter<I: IntoIterator<Item = Option<T>>>(iter: I) -> Self {
+        Self::from_ref_iter(iter)
+    }
+}
+
+/// Two arrays are equal if they have the same nulls and equal values at non-null positions. Note
+/// that `NaN` is not equal to itself, so float arrays containing `NaN` never equal each other.
+impl<T: PrimitiveType + PartialEq> PartialEq for PrimitiveArray<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bitmap == other.bitmap
+            && self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .zip(self.bitmap.iter())
+                .all(|((a, b), valid)| !*valid || a == b)
+    }
+}
+
+impl<T: PrimitiveType> PrimitiveArray<T> {
+    /// Get the null bitmap, where a set bit means the value at that position is not null.
+    pub fn null_bitmap(&self) -> &BitVec {
+        &self.bitmap
+    }
+
+    /// Borrow the contiguous value buffer and the null bitmap. Values at null positions are
+    /// placeholders. Arrow owns its buffers, so building an Arrow array from them still copies
+    /// both, see `to_arrow` of the `arrow` feature.
+    ///
+    /// The bitmap is a `BitVec<usize, Lsb0>`, so bit `i` is the `i % usize::BITS`-th least
+    /// significant bit of the `i / usize::BITS`-th word. This matches the byte layout of Arrow's
+    /// validity buffer only on little-endian targets. Use `to_arrow` of the `arrow` feature or
+    /// [`ArrayImpl::validity_bytes`] for a portable layout.
+    pub fn as_arrow_slice(&self) -> (&[T], &BitVec) {
+        (&self.data, &self.bitmap)
+    }
+
+    /// Get elements in `range` as a new array.
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+        let range = resolve_range(range, self.data.len());
+        Self {
+            data: self.data[range.clone()].to_vec(),
+            bitmap: self.bitmap[range].to_bitvec(),
+        }
+    }
+
+    /// Build a new array from the elements at `indices`. An index may appear more than once.
+    ///
+    /// Panics if any index is out of bounds.
+    pub fn take(&self, indices: &[usize]) -> Self {
+        let mut data = Vec::with_capacity(indices.len());
+        let mut bitmap = BitVec::with_capacity(indices.len());
+        for &idx in indices {
+            assert!(
+                idx < self.data.len(),
+                "index {} out of bounds for array of length {}",
+                idx,
+                self.data.len()
+            );
+            data.push(self.data[idx].clone());
+            bitmap.push(self.bitmap[idx]);
+        }
+        Self { data, bitmap }
+    }
+}
+
+/// A [`PrimitiveType`] that has the same memory layout as an Arrow primitive type.
+#[cfg(feature = "arrow")]
+pub trait ArrowPrimitive: PrimitiveType + arrow::datatypes::ArrowNativeType {
+    type ArrowType: arrow::datatypes::ArrowPrimitiveType<Native = Self>;
+}
+
+/// Implements [`ArrowPrimitive`] for types that are native types of Arrow.
+#[cfg(feature = "arrow")]
+macro_rules! impl_arrow_primitive {
+    ($({ $t:ty, $arrow:ty }),*) => {
+        $(
+            impl ArrowPrimitive for $t {
+                type ArrowType = $arrow;
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "arrow")]
+impl_arrow_primitive! {
+    { i16, arrow::datatypes::Int16Type },
+    { i32, arrow::datatypes::Int32Type },
+    { i64, arrow::datatypes::Int64Type },
+    { f32, arrow::datatypes::Float32Type },
+    { f64, arrow::datatypes::Float64Type }
+}
+
+#[cfg(feature = "arrow")]
+impl<T: ArrowPrimitive> PrimitiveArray<T> {
+    /// Convert to an Arrow array. The values are copied into an Arrow buffer as a whole, and the
+    /// null bitmap is repacked into Arrow's LSB-first byte layout.
+    pub fn to_arrow(&self) -> arrow::array::PrimitiveArray<T::ArrowType> {
+        use arrow::array::ArrayData;
+        use arrow::buffer::Buffer;
+        use bitvec::prelude::Lsb0;
+
+        let (values, bitmap) = self.as_arrow_slice();
+        let validity = bitmap.iter().by_vals().collect::<BitVec<u8, Lsb0>>();
+        let data =
+            ArrayData::builder(<T::ArrowType as arrow::datatypes::ArrowPrimitiveType>::DATA_TYPE)
+                .len(values.len())
+                .add_buffer(Buffer::from_slice_ref(&values))
+                .null_bit_buffer(Buffer::from(validity.into_vec()))
+                .build()
+                .expect("buffers of a primitive array are always valid");
+        arrow::array::PrimitiveArray::from(data)
+    }
+}
+
+impl<T: PrimitiveType + Copy> PrimitiveArray<T> {
+    /// Apply `f` to each pair of elements of `self` and `other`. The null bitmap of the output is
+    /// the bitwise AND of the two input bitmaps, and `f` is only called where both inputs are
+    /// valid, so that nulls are handled word by word instead of row by row.
+    ///
+    /// Panics if `other` is not of the same length.
+    pub fn binary<U, O, F>(&self, other: &PrimitiveArray<U>, f: F) -> PrimitiveArray<O>
+    where
+        U: PrimitiveType + Copy,
+        O: PrimitiveType,
+        F: Fn(T, U) -> O,
+    {
+        assert_eq!(self.data.len(), other.data.len(), "array length mismatch");
+        let mut bitmap = self.bitmap.clone();
+        bitmap &= other.bitmap.as_bitslice();
+        let data = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .zip(bitmap.iter().by_vals())
+            .map(|((a, b), valid)| if valid { f(*a, *b) } else { O::placeholder() })
+            .collect();
+        PrimitiveArray { data, bitmap }
+    }
+}
+
+impl<T: SortKey + Copy> PrimitiveArray<T> {
+    /// Map each element to its [`SortKey`], so that a stable radix sort over the keys produces the
+    /// same permutation as [`ArrayImpl::sort_to_indices`] in ascending order.
+    ///
+    /// Keys of values are offset by one into `[1, 2^64]`, and nulls map to `0` if `nulls_first`,
+    /// otherwise `u128::MAX`, so that nulls never tie with values.
+    pub fn to_sort_keys(&self, nulls_first: bool) -> Vec<u128> {
+        let null_key = if nulls_first { 0 } else { u128::MAX };
+        self.data
+            .iter()
+            .zip(self.bitmap.iter())
+            .map(|(v, valid)| {
+                if *valid {
+                    v.sort_key() as u128 + 1
+                } else {
+                    null_key
+                }
+            })
+            .collect()
+    }
+}
+
+impl<T> PrimitiveArray<T>
+where
+    T: PrimitiveType + Copy + CheckedArith,
+{
+    /// Get the running total of the array. Each element is the sum of all non-null elements up to
+    /// and including that position. A null element stays null, and doesn't reset the total.
+    ///
+    /// Returns an error on integer or decimal overflow instead of wrapping or panicking.
+    pub fn cumulative_sum(&self) -> Result<Self> {
+        let mut sum: Option<T> = None;
+        let data = self
+            .data
+            .iter()
+            .zip(self.bitmap.iter())
+            .map(|(&v, valid)| {
+                if *valid {
+                    let s = match sum {
+                        Some(s) => s
+                            .checked_add(v)
+                            .ok_or_else(|| anyhow!("overflow in cumulative sum"))?,
+                        None => v,
+                    };
+                    sum = Some(s);
+                    Ok(s)
+                } else {
+                    Ok(T::placeholder())
+                }
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            data,
+            bitmap: self.bitmap.clone(),
+        })
+    }
+}
+
 /// [`ArrayBuilder`] for [`PrimitiveType`].
 pub struct PrimitiveArrayBuilder<T: PrimitiveType> {
     /// The actual data of this array.
@@ -131,12 +450,34 @@ where
                 self.bitmap.push(true);
             }
             None => {
-                self.data.push(T::default());
+                self.data.push(T::placeholder());
                 self.bitmap.push(false);
             }
         }
     }
 
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.data.try_reserve(additional)?;
+        self.bitmap.reserve(additional);
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        self.bitmap.reserve(additional);
+    }
+
+    fn append_n(&mut self, n: usize, value: Option<T>) {
+        let len = self.data.len();
+        self.data
+            .resize(len + n, value.unwrap_or_else(T::placeholder));
+        self.bitmap.resize(len + n, value.is_some());
+    }
+
     fn finish(self) -> Self::Array {
         PrimitiveArray {
             data: self.data,
@@ -144,3 +485,276 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_iter() {
+        let array: I32Array = (0..5).map(Some).collect();
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some(0), Some(1), Some(2), Some(3), Some(4)]
+        );
+
+        let array = I32Array::from_ref_iter([Some(1), None]);
+        assert_eq!(array.iter().collect::<Vec<_>>(), vec![Some(1), None]);
+    }
+
+    #[test]
+    fn test_append_n() {
+        let mut builder = I32ArrayBuilder::with_capacity(0);
+        builder.reserve(10);
+        builder.push(Some(1));
+        builder.append_n(3, Some(7));
+        builder.append_n(2, None);
+        let array = builder.finish();
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some(1), Some(7), Some(7), Some(7), None, None]
+        );
+
+        let mut builder = I32ArrayBuilder::with_capacity(0);
+        builder.reserve(10);
+        assert!(builder.finish().is_empty());
+    }
+
+    #[test]
+    fn test_array_eq() {
+        let array = I32Array::from_slice(&[Some(1), None, Some(3)]);
+        assert_eq!(array, I32Array::from_slice(&[Some(1), None, Some(3)]));
+        assert_ne!(array, I32Array::from_slice(&[Some(1), Some(3), None]));
+        assert_ne!(array, I32Array::from_slice(&[Some(1), Some(0), Some(3)]));
+        assert_ne!(array, I32Array::from_slice(&[Some(1), None]));
+        // Values under nulls are not compared.
+        let other = I32Array {
+            data: vec![1, 2, 3],
+            bitmap: [true, false, true].into_iter().collect(),
+        };
+        assert_eq!(array, other);
+        assert_eq!(I32Array::from_slice(&[]), I32Array::from_slice(&[]));
+
+        let array = F64Array::from_slice(&[Some(f64::NAN)]);
+        assert_ne!(array, array.clone());
+    }
+
+    #[test]
+    fn test_as_arrow_slice() {
+        let array = I32Array::from_slice(&[Some(1), None, Some(3)]);
+        let (values, bitmap) = array.as_arrow_slice();
+        assert_eq!(values, &[1, 0, 3]);
+        assert_eq!(
+            bitmap.iter().by_vals().collect::<Vec<_>>(),
+            vec![true, false, true]
+        );
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_to_arrow() {
+        use arrow::array::Array as _;
+
+        let data = (0..20)
+            .map(|x| if x % 3 == 0 { None } else { Some(x) })
+            .collect::<Vec<_>>();
+        let array = I32Array::from_slice(&data);
+        let arrow_array = array.to_arrow();
+        assert_eq!(arrow_array.len(), 20);
+        assert_eq!(arrow_array.null_count(), 7);
+        assert_eq!(arrow_array.iter().collect::<Vec<_>>(), data);
+        assert_eq!(arrow_array.iter().collect::<I32Array>(), array);
+    }
+
+    #[test]
+    fn test_null_count() {
+        let array = I32Array::from_slice(&[Some(1), None, Some(3), None, None]);
+        assert_eq!(array.null_count(), 3);
+        assert!(array.is_null(1));
+        assert!(!array.is_null(2));
+        assert_eq!(array.null_bitmap().count_ones(), 2);
+        assert_eq!(I32Array::from_slice(&[]).null_count(), 0);
+    }
+
+    /// Stable LSD radix sort over 8-bit digits.
+    fn radix_sort_indices(keys: &[u128]) -> Vec<usize> {
+        let mut indices = (0..keys.len()).collect::<Vec<_>>();
+        for shift in (0..128).step_by(8) {
+            let mut buckets = vec![vec![]; 256];
+            for idx in indices {
+                buckets[(keys[idx] >> shift) as usize & 0xff].push(idx);
+            }
+            indices = buckets.concat();
+        }
+        indices
+    }
+
+    #[test]
+    fn test_radix_sort_keys() {
+        let array = I32Array::from_slice(&[
+            Some(3),
+            None,
+            Some(-1),
+            Some(i32::MIN),
+            Some(0),
+            None,
+            Some(i32::MAX),
+            Some(-1),
+        ]);
+        let array_impl = ArrayImpl::from(array.clone());
+        for nulls_first in [false, true] {
+            assert_eq!(
+                radix_sort_indices(&array.to_sort_keys(nulls_first)),
+                array_impl.sort_to_indices(false, nulls_first)
+            );
+        }
+
+        let array = F64Array::from_slice(&[Some(1.5), Some(-2.0), None, Some(0.0), Some(-0.5)]);
+        let array_impl = ArrayImpl::from(array.clone());
+        assert_eq!(
+            radix_sort_indices(&array.to_sort_keys(true)),
+            array_impl.sort_to_indices(false, true)
+        );
+    }
+
+    #[test]
+    fn test_radix_sort_keys_extremes() {
+        let array = I64Array::from_slice(&[
+            Some(i64::MAX),
+            None,
+            Some(i64::MIN),
+            Some(0),
+            None,
+            Some(i64::MIN),
+            Some(i64::MAX),
+        ]);
+        let array_impl = ArrayImpl::from(array.clone());
+        for nulls_first in [false, true] {
+            assert_eq!(
+                radix_sort_indices(&array.to_sort_keys(nulls_first)),
+                array_impl.sort_to_indices(false, nulls_first)
+            );
+        }
+
+        let array = F64Array::from_slice(&[
+            Some(f64::NAN),
+            None,
+            Some(f64::INFINITY),
+            Some(-0.0),
+            Some(f64::NEG_INFINITY),
+            Some(0.0),
+            Some(-f64::NAN),
+            Some(f64::MIN),
+        ]);
+        let array_impl = ArrayImpl::from(array.clone());
+        for nulls_first in [false, true] {
+            assert_eq!(
+                radix_sort_indices(&array.to_sort_keys(nulls_first)),
+                array_impl.sort_to_indices(false, nulls_first)
+            );
+        }
+    }
+
+    #[test]
+    fn test_slice() {
+        let array = I32Array::from_slice(&[Some(1), None, Some(3)]);
+        assert_eq!(
+            array.slice(1..).iter().collect::<Vec<_>>(),
+            vec![None, Some(3)]
+        );
+        assert_eq!(array.slice(..1).iter().collect::<Vec<_>>(), vec![Some(1)]);
+        assert_eq!(array.slice(..).len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice range 2..4 out of bounds for array of length 3")]
+    fn test_slice_out_of_bounds() {
+        I32Array::from_slice(&[Some(1), None, Some(3)]).slice(2..4);
+    }
+
+    #[test]
+    fn test_take() {
+        let array = I32Array::from_slice(&[Some(1), None, Some(3)]);
+        assert_eq!(
+            array.take(&[2, 1, 2, 0]).iter().collect::<Vec<_>>(),
+            vec![Some(3), None, Some(3), Some(1)]
+        );
+        assert!(array.take(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 out of bounds for array of length 3")]
+    fn test_take_out_of_bounds() {
+        I32Array::from_slice(&[Some(1), None, Some(3)]).take(&[0, 3]);
+    }
+
+    #[test]
+    fn test_cumulative_sum() {
+        let array = I64Array::from_slice(&[Some(1), None, Some(3)]);
+        assert_eq!(
+            array.cumulative_sum().unwrap().iter().collect::<Vec<_>>(),
+            vec![Some(1), None, Some(4)]
+        );
+
+        let array = F64Array::from_slice(&[None, Some(0.5), Some(1.0)]);
+        assert_eq!(
+            array.cumulative_sum().unwrap().iter().collect::<Vec<_>>(),
+            vec![None, Some(0.5), Some(1.5)]
+        );
+
+        let array =
+            DecimalArray::from_slice(&[Some(Decimal::new(15, 1)), Some(Decimal::new(1, 0))]);
+        assert_eq!(
+            array.cumulative_sum().unwrap().iter().collect::<Vec<_>>(),
+            vec![Some(Decimal::new(15, 1)), Some(Decimal::new(25, 1))]
+        );
+
+        let array = I32Array::from_slice(&[Some(i32::MAX), None, Some(1)]);
+        assert!(array.cumulative_sum().is_err());
+        let array = I32Array::from_slice(&[Some(i32::MAX), None, Some(-1)]);
+        assert_eq!(
+            array.cumulative_sum().unwrap().iter().collect::<Vec<_>>(),
+            vec![Some(i32::MAX), None, Some(i32::MAX - 1)]
+        );
+    }
+
+    #[test]
+    fn test_get_unchecked() {
+        let array: I32Array = (0..10000)
+            .map(|x| if x % 3 == 0 { None } else { Some(x) })
+            .collect();
+        let mut sum = 0i64;
+        for idx in 0..array.len() {
+            let value = unsafe { array.get_unchecked(idx) };
+            assert_eq!(value, array.get(idx));
+            sum += value.unwrap_or_default() as i64;
+        }
+        assert_eq!(sum, (0..10000).filter(|x| x % 3 != 0).sum::<i64>());
+    }
+
+    #[test]
+    fn test_binary() {
+        let a = I32Array::from_slice(&[Some(1), None, Some(3), None]);
+        let b = I64Array::from_slice(&[Some(10), Some(20), None, None]);
+        let result = a.binary(&b, |a, b| a as i64 + b);
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(11), None, None, None]
+        );
+        assert_eq!(
+            result.null_bitmap(),
+            &(a.null_bitmap().clone() & b.null_bitmap().as_bitslice())
+        );
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut builder = I32ArrayBuilder::with_capacity(0);
+        builder.extend([Some(1), None]);
+        builder.extend((2..4).map(Some));
+        assert_eq!(
+            builder.finish().iter().collect::<Vec<_>>(),
+            vec![Some(1), None, Some(2), Some(3)]
+        );
+    }
+}
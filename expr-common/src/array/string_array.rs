@@ -8,9 +8,13 @@
 //! * It is of variable length, and its storage layout is different from others.
 //! * You can only get an `&str` from a `StringArray` (instead of `&String`).
 
+use std::borrow::Cow;
+use std::collections::TryReserveError;
+use std::ops::RangeBounds;
+
 use bitvec::prelude::BitVec;
 
-use super::{Array, ArrayBuilder, ArrayIterator};
+use super::{resolve_range, Array, ArrayBuilder, ArrayIterator, PhysicalType};
 
 /// An [`Array`] that stores [`String`]
 #[derive(Clone)]
@@ -33,6 +37,8 @@ impl Array for StringArray {
     /// For [`StringArray`], we can only get an `&str` out of it with zero overhead.
     type RefItem<'a> = &'a str;
 
+    const PHYSICAL_TYPE: PhysicalType = PhysicalType::String;
+
     fn get(&self, idx: usize) -> Option<&str> {
         if self.bitmap[idx] {
             let range = self.offsets[idx]..self.offsets[idx + 1];
@@ -42,15 +48,116 @@ impl Array for StringArray {
         }
     }
 
+    unsafe fn get_unchecked(&self, idx: usize) -> Option<&str> {
+        if *self.bitmap.get_unchecked(idx) {
+            // `offsets` always has `len + 1` entries.
+            let range = *self.offsets.get_unchecked(idx)..*self.offsets.get_unchecked(idx + 1);
+            Some(std::str::from_utf8_unchecked(
+                self.data.get_unchecked(range),
+            ))
+        } else {
+            None
+        }
+    }
+
     fn len(&self) -> usize {
         self.bitmap.len()
     }
 
+    fn is_null(&self, idx: usize) -> bool {
+        !self.bitmap[idx]
+    }
+
+    fn null_count(&self) -> usize {
+        self.bitmap.count_zeros()
+    }
+
     fn iter(&self) -> ArrayIterator<Self> {
         ArrayIterator::new(self)
     }
 }
 
+impl<'a> FromIterator<Option<&'a str>> for StringArray {
+    fn from_iter<I: IntoIterator<Item = Option<&'a str>>>(iter: I) -> Self {
+        Self::from_ref_iter(iter)
+    }
+}
+
+impl FromIterator<Option<String>> for StringArray {
+    fn from_iter<I: IntoIterator<Item = Option<String>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut builder = StringArrayBuilder::with_capacity(iter.size_hint().0);
+        for item in iter {
+            builder.push(item.as_deref());
+        }
+        builder.finish()
+    }
+}
+
+/// Two arrays are equal if they have the same nulls and equal strings at non-null positions. The
+/// layout of the data buffer is not compared, so an array equals its compacted copy.
+impl PartialEq for StringArray {
+    fn eq(&self, other: &Self) -> bool {
+        self.bitmap == other.bitmap && self.iter().eq(other.iter())
+    }
+}
+
+impl StringArray {
+    /// Get the null bitmap, where a set bit means the value at that position is not null.
+    pub fn null_bitmap(&self) -> &BitVec {
+        &self.bitmap
+    }
+
+    /// Get number of bytes in the data buffer, including bytes not referenced by any element.
+    pub fn buffer_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Get elements in `range` as a new array.
+    ///
+    /// Only the bytes of the elements in `range` are copied, and the offsets are rebased to the
+    /// start of them.
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> StringArray {
+        let range = resolve_range(range, self.len());
+        let offsets = &self.offsets[range.start..range.end + 1];
+        let (start, end) = (offsets[0], offsets[offsets.len() - 1]);
+        StringArray {
+            data: self.data[start..end].to_vec(),
+            offsets: offsets.iter().map(|offset| offset - start).collect(),
+            bitmap: self.bitmap[range].to_bitvec(),
+        }
+    }
+
+    /// Build a new array from the elements at `indices`. An index may appear more than once.
+    ///
+    /// Panics if any index is out of bounds.
+    pub fn take(&self, indices: &[usize]) -> StringArray {
+        let mut builder = StringArrayBuilder::with_capacity(indices.len());
+        for &idx in indices {
+            assert!(
+                idx < self.len(),
+                "index {} out of bounds for array of length {}",
+                idx,
+                self.len()
+            );
+            builder.push(self.get(idx));
+        }
+        builder.finish()
+    }
+
+    /// Rebuild the data buffer and offsets so that they only contain bytes of the elements.
+    pub fn compact(&self) -> StringArray {
+        let bytes = self.iter().flatten().map(str::len).sum();
+        let mut builder = StringArrayBuilder::with_data_capacity(self.len(), bytes);
+        for value in self.iter() {
+            builder.push(value);
+        }
+        builder.finish()
+    }
+}
+
 /// [`ArrayBuilder`] for [`String`].
 pub struct StringArrayBuilder {
     /// The flattened data of string.
@@ -63,6 +170,27 @@ pub struct StringArrayBuilder {
     bitmap: BitVec,
 }
 
+impl StringArrayBuilder {
+    /// Create a builder for `capacity` strings, whose data buffer can hold `data_capacity` bytes
+    /// without reallocating.
+    pub fn with_data_capacity(capacity: usize, data_capacity: usize) -> Self {
+        let mut builder = Self::with_capacity(capacity);
+        builder.data.reserve(data_capacity);
+        builder
+    }
+
+    /// Get number of bytes the data buffer can hold without reallocating.
+    pub fn data_capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Append a value that is either borrowed or owned. The bytes are copied into the builder
+    /// directly, so a borrowed value doesn't need to be allocated as a `String` first.
+    pub fn push_cow(&mut self, value: Option<Cow<'_, str>>) {
+        self.push(value.as_deref());
+    }
+}
+
 impl ArrayBuilder for StringArrayBuilder {
     type Array = StringArray;
 
@@ -90,6 +218,56 @@ impl ArrayBuilder for StringArrayBuilder {
         }
     }
 
+    fn len(&self) -> usize {
+        self.bitmap.len()
+    }
+
+    /// Reserve for `additional` strings. The data buffer is reserved by one byte per string, which
+    /// is the same as [`ArrayBuilder::with_capacity`].
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.offsets.try_reserve(additional)?;
+        self.data.try_reserve(additional)?;
+        self.bitmap.reserve(additional);
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.offsets.reserve(additional);
+        self.data.reserve(additional);
+        self.bitmap.reserve(additional);
+    }
+
+    /// Append all strings of `iter`. The strings are collected first, so that the data buffer is
+    /// reserved for the total bytes of all strings at once.
+    fn extend<'a, I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Option<<Self::Array as Array>::RefItem<'a>>>,
+    {
+        let values = iter.into_iter().collect::<Vec<_>>();
+        let bytes = values.iter().flatten().map(|v| v.len()).sum();
+        self.offsets.reserve(values.len());
+        self.data.reserve(bytes);
+        self.bitmap.reserve(values.len());
+        for value in values {
+            self.push(value);
+        }
+    }
+
+    fn append_n(&mut self, n: usize, value: Option<&str>) {
+        self.offsets.reserve(n);
+        let value = value.map(str::as_bytes);
+        if let Some(v) = value {
+            self.data.reserve(v.len() * n);
+        }
+        for _ in 0..n {
+            if let Some(v) = value {
+                self.data.extend_from_slice(v);
+            }
+            self.offsets.push(self.data.len());
+        }
+        self.bitmap.resize(self.bitmap.len() + n, value.is_some());
+    }
+
     fn finish(self) -> Self::Array {
         StringArray {
             data: self.data,
@@ -98,3 +276,185 @@ impl ArrayBuilder for StringArrayBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::ArrayImpl;
+    use crate::scalar::ScalarRefImpl;
+
+    #[test]
+    fn test_from_iter() {
+        let data = vec![Some("a".to_string()), None, Some("bc".to_string())];
+        let array: StringArray = data.into_iter().collect();
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some("a"), None, Some("bc")]
+        );
+
+        let array: StringArray = array.iter().skip(1).collect();
+        assert_eq!(array.iter().collect::<Vec<_>>(), vec![None, Some("bc")]);
+    }
+
+    #[test]
+    fn test_append_n() {
+        let mut builder = StringArrayBuilder::with_capacity(0);
+        builder.reserve(4);
+        builder.append_n(2, Some("ab"));
+        builder.append_n(1, None);
+        builder.push(Some("c"));
+        let array = builder.finish();
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some("ab"), Some("ab"), None, Some("c")]
+        );
+        assert_eq!(array.buffer_len(), 5);
+
+        let mut builder = StringArrayBuilder::with_capacity(0);
+        builder.reserve(4);
+        assert!(builder.finish().is_empty());
+    }
+
+    #[test]
+    fn test_slice_copies_referenced_bytes() {
+        let array = StringArray::from_slice(&[Some("aaa"), Some("bb"), None, Some("c")]);
+        let sliced = array.slice(1..3);
+        assert_eq!(sliced.iter().collect::<Vec<_>>(), vec![Some("bb"), None]);
+        assert_eq!(sliced.buffer_len(), 2);
+
+        let sliced = array.slice(3..);
+        assert_eq!(sliced.iter().collect::<Vec<_>>(), vec![Some("c")]);
+        assert_eq!(sliced.buffer_len(), 1);
+        assert_eq!(array.slice(2..2).buffer_len(), 0);
+    }
+
+    #[test]
+    fn test_slice_compact() {
+        let array = StringArray::from_slice(&[Some("aaa"), Some("bb"), None, Some("c")]);
+        let sliced = array.slice(1..3);
+        let compacted = sliced.compact();
+        assert_eq!(compacted.iter().collect::<Vec<_>>(), vec![Some("bb"), None]);
+        assert_eq!(compacted.buffer_len(), 2);
+
+        // An array whose data buffer still holds the bytes of the elements around it.
+        let array = StringArray {
+            data: array.data.clone(),
+            offsets: array.offsets[1..4].to_vec(),
+            bitmap: array.bitmap[1..3].to_bitvec(),
+        };
+        assert_eq!(array.buffer_len(), 6);
+        let compacted = array.compact();
+        assert_eq!(compacted, sliced);
+        assert_eq!(compacted.iter().collect::<Vec<_>>(), vec![Some("bb"), None]);
+        assert_eq!(compacted.buffer_len(), 2);
+    }
+
+    #[test]
+    fn test_array_eq() {
+        let array = StringArray::from_slice(&[Some("aaa"), Some("bb"), None, Some("c")]);
+        let sliced = array.slice(1..3);
+        assert_eq!(sliced, sliced.compact());
+        assert_eq!(sliced, StringArray::from_slice(&[Some("bb"), None]));
+        assert_ne!(sliced, StringArray::from_slice(&[None, Some("bb")]));
+        assert_ne!(sliced, StringArray::from_slice(&[Some("bb"), Some("")]));
+        assert_ne!(sliced, StringArray::from_slice(&[Some("bb")]));
+    }
+
+    #[test]
+    fn test_null_count() {
+        let array = StringArray::from_slice(&[None, Some("a"), None]);
+        assert_eq!(array.null_count(), 2);
+        assert!(array.is_null(0));
+        assert!(!array.null_bitmap()[0]);
+    }
+
+    #[test]
+    fn test_slice_bounds() {
+        let array = StringArray::from_slice(&[Some("a"), None, Some("c")]);
+        assert_eq!(
+            array.slice(1..).iter().collect::<Vec<_>>(),
+            vec![None, Some("c")]
+        );
+        assert_eq!(
+            array.slice(..=0).iter().collect::<Vec<_>>(),
+            vec![Some("a")]
+        );
+        assert!(array.slice(3..).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_slice_out_of_bounds() {
+        StringArray::from_slice(&[Some("a")]).slice(0..2);
+    }
+
+    #[test]
+    fn test_take() {
+        let array = StringArray::from_slice(&[Some("a"), None, Some("c")]);
+        assert_eq!(
+            array.take(&[2, 2, 1, 0]).iter().collect::<Vec<_>>(),
+            vec![Some("c"), Some("c"), None, Some("a")]
+        );
+
+        let array: ArrayImpl = array.into();
+        assert_eq!(array.take(&[1, 0]).get(1), Some(ScalarRefImpl::String("a")));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_take_out_of_bounds() {
+        StringArray::from_slice(&[Some("a")]).take(&[1]);
+    }
+
+    #[test]
+    fn test_push_cow() {
+        let mut builder = StringArrayBuilder::with_capacity(3);
+        builder.push_cow(Some(Cow::Borrowed("abc")));
+        builder.push_cow(None);
+        builder.push_cow(Some(Cow::Owned("ABC".to_uppercase())));
+        let array = builder.finish();
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some("abc"), None, Some("ABC")]
+        );
+    }
+
+    #[test]
+    fn test_get_unchecked() {
+        let array = StringArray::from_slice(&[Some("a"), None, Some(""), Some("你好")]);
+        for idx in 0..array.len() {
+            assert_eq!(unsafe { array.get_unchecked(idx) }, array.get(idx));
+        }
+    }
+
+    #[test]
+    fn test_extend() {
+        let values = (0..1000)
+            .map(|i| (i % 10 != 0).then(|| i.to_string()))
+            .collect::<Vec<_>>();
+        let bytes = values.iter().flatten().map(|v| v.len()).sum::<usize>();
+
+        let mut builder = StringArrayBuilder::with_capacity(0);
+        builder.extend(values.iter().map(|v| v.as_deref()));
+        // The data buffer is allocated exactly once for all strings.
+        assert_eq!(builder.data_capacity(), bytes);
+        let array = builder.finish();
+        assert_eq!(array.len(), 1000);
+        for (value, expected) in array.iter().zip(values.iter()) {
+            assert_eq!(value, expected.as_deref());
+        }
+    }
+
+    #[test]
+    fn test_push_owned() {
+        let mut builder = StringArrayBuilder::with_capacity(0);
+        for i in 0..3 {
+            builder.push_owned(Some(format!("{}{}", i, i)));
+        }
+        builder.push_owned(None);
+        assert_eq!(
+            builder.finish().iter().collect::<Vec<_>>(),
+            vec![Some("00"), Some("11"), Some("22"), None]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+use bitvec::prelude::BitVec;
+
+use super::ArrayImpl;
+
+/// An array of structs, stored as one [`ArrayImpl`] per field plus a top-level null bitmap.
+///
+/// Field arrays keep their own validity, which may be set even at rows where the struct itself
+/// is null. Use [`StructArray::field`] to read a field with the struct-level nulls applied.
+///
+/// There is no struct scalar yet, so `StructArray` is not a variant of [`ArrayImpl`].
+#[derive(Debug, Clone)]
+pub struct StructArray {
+    /// The columns of each field.
+    fields: Vec<ArrayImpl>,
+
+    /// The null bitmap of this array.
+    bitmap: BitVec,
+}
+
+impl StructArray {
+    /// Create a struct array from field columns and the top-level null bitmap. All fields must
+    /// be of the same length as the bitmap.
+    pub fn new(fields: Vec<ArrayImpl>, bitmap: BitVec) -> Self {
+        for field in &fields {
+            assert_eq!(field.len(), bitmap.len(), "field length mismatch");
+        }
+        Self { fields, bitmap }
+    }
+
+    /// Get number of fields in the struct.
+    pub fn num_fields(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Get number of rows in the array.
+    pub fn len(&self) -> usize {
+        self.bitmap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns if the struct at `idx` is null.
+    pub fn is_null(&self, idx: usize) -> bool {
+        !self.bitmap[idx]
+    }
+
+    /// Get the column of field `idx`. A row is null if either the struct or the field value is
+    /// null.
+    pub fn field(&self, idx: usize) -> ArrayImpl {
+        self.fields[idx].mask_combine(&self.bitmap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::{Array, I32Array, StringArray};
+
+    #[test]
+    fn test_struct_field() {
+        let array = StructArray::new(
+            vec![
+                I32Array::from_slice(&[Some(1), Some(2), None]).into(),
+                StringArray::from_slice(&[Some("a"), None, Some("c")]).into(),
+            ],
+            [true, false, true].into_iter().collect(),
+        );
+        assert_eq!(array.num_fields(), 2);
+        assert!(array.is_null(1));
+
+        let f0: I32Array = array.field(0).try_into().unwrap();
+        assert_eq!(f0.iter().collect::<Vec<_>>(), vec![Some(1), None, None]);
+        let f1: StringArray = array.field(1).try_into().unwrap();
+        assert_eq!(
+            f1.iter().collect::<Vec<_>>(),
+            vec![Some("a"), None, Some("c")]
+        );
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Contains [`Chunk`], a batch of columns of the same length
+
+use std::cmp::Ordering;
+
+use anyhow::{anyhow, Result};
+
+use crate::array::{Array, ArrayImpl, BoolArray};
+use crate::datatype::DataType;
+
+/// A collection of arrays of the same length, which is the unit of data passed between
+/// executors.
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    columns: Vec<ArrayImpl>,
+}
+
+impl Chunk {
+    /// Create a chunk from columns. All columns must be of the same length.
+    pub fn new(columns: Vec<ArrayImpl>) -> Self {
+        if let Some(first) = columns.first() {
+            for column in &columns {
+                assert_eq!(column.len(), first.len(), "column length mismatch");
+            }
+        }
+        Self { columns }
+    }
+
+    /// Get all columns of the chunk.
+    pub fn columns(&self) -> &[ArrayImpl] {
+        &self.columns
+    }
+
+    /// Get the column at `idx`.
+    pub fn column(&self, idx: usize) -> &ArrayImpl {
+        &self.columns[idx]
+    }
+
+    /// Get number of rows in the chunk.
+    pub fn cardinality(&self) -> usize {
+        self.columns.first().map_or(0, |column| column.len())
+    }
+
+    /// Check that the chunk has one column for each type in `schema`, and that each column is of
+    /// the physical type of the corresponding [`DataType`].
+    pub fn validate(&self, schema: &[DataType]) -> Result<()> {
+        if self.columns.len() != schema.len() {
+            return Err(anyhow!(
+                "column count mismatch: expect {}, get {}",
+                schema.len(),
+                self.columns.len()
+            ));
+        }
+        for (idx, (column, data_type)) in self.columns.iter().zip(schema).enumerate() {
+            let expected = data_type.physical_type();
+            if column.physical_type() != expected {
+                return Err(anyhow!(
+                    "type mismatch at column {}: expect {} ({:?}), get {:?}",
+                    idx,
+                    data_type,
+                    expected,
+                    column.physical_type()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build a new chunk from the rows at `indices`.
+    pub fn take(&self, indices: &[usize]) -> Chunk {
+        Self {
+            columns: self.columns.iter().map(|c| c.take(indices)).collect(),
+        }
+    }
+
+    /// Keep rows where `mask` is true, and only columns in `cols`, in one pass. A null in `mask`
+    /// is treated as false.
+    ///
+    /// The selected rows are computed once and applied to the projected columns only, so
+    /// columns that are not projected are never copied.
+    pub fn filter_project(&self, mask: &BoolArray, cols: &[usize]) -> Result<Chunk> {
+        if mask.len() != self.cardinality() {
+            return Err(anyhow!(
+                "mask length mismatch: expect {}, get {}",
+                self.cardinality(),
+                mask.len()
+            ));
+        }
+        if let Some(col) = cols.iter().find(|&&col| col >= self.columns.len()) {
+            return Err(anyhow!(
+                "column {} out of range, the chunk has {} columns",
+                col,
+                self.columns.len()
+            ));
+        }
+        let indices = mask
+            .iter()
+            .enumerate()
+            .filter(|(_, selected)| *selected == Some(true))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        Ok(Self {
+            columns: cols
+                .iter()
+                .map(|&col| self.columns[col].take(&indices))
+                .collect(),
+        })
+    }
+
+    /// Get the permutation that stably sorts the chunk by `keys`. Each key is `(column index,
+    /// desc, nulls_first)`, and ties on a key are broken by the next one.
+    pub fn sort_to_indices(&self, keys: &[(usize, bool, bool)]) -> Vec<usize> {
+        let mut indices = (0..self.cardinality()).collect::<Vec<_>>();
+        indices.sort_by(|&i, &j| {
+            keys.iter()
+                .map(|&(col, desc, nulls_first)| {
+                    self.columns[col].compare_at(i, j, desc, nulls_first)
+                })
+                .find(|ord| *ord != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        });
+        indices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::{I32Array, StringArray};
+
+    #[test]
+    fn test_sort_chunk() {
+        let chunk = Chunk::new(vec![
+            I32Array::from_slice(&[Some(2), Some(1), Some(2), None, Some(1)]).into(),
+            StringArray::from_slice(&[Some("a"), Some("b"), Some("c"), Some("d"), None]).into(),
+        ]);
+        let indices = chunk.sort_to_indices(&[(0, false, false), (1, true, false)]);
+        assert_eq!(indices, vec![1, 4, 2, 0, 3]);
+
+        let sorted = chunk.take(&indices);
+        let c0: &I32Array = sorted.column(0).try_into().unwrap();
+        let c1: &StringArray = sorted.column(1).try_into().unwrap();
+        assert_eq!(
+            c0.iter().collect::<Vec<_>>(),
+            vec![Some(1), Some(1), Some(2), Some(2), None]
+        );
+        assert_eq!(
+            c1.iter().collect::<Vec<_>>(),
+            vec![Some("b"), None, Some("c"), Some("a"), Some("d")]
+        );
+    }
+
+    #[test]
+    fn test_filter_project() {
+        let chunk = Chunk::new(vec![
+            I32Array::from_slice(&[Some(1), Some(2), None, Some(4)]).into(),
+            StringArray::from_slice(&[Some("a"), Some("b"), Some("c"), Some("d")]).into(),
+            I32Array::from_slice(&[Some(10), None, Some(30), Some(40)]).into(),
+        ]);
+        let mask = BoolArray::from_slice(&[Some(true), Some(false), Some(true), None]);
+        let result = chunk.filter_project(&mask, &[2, 0]).unwrap();
+
+        // Compare with filtering all columns first, and then projecting.
+        let filtered = chunk.take(&[0, 2]);
+        let expected = Chunk::new(vec![filtered.column(2).clone(), filtered.column(0).clone()]);
+        assert_eq!(result.cardinality(), 2);
+        for (a, b) in result.columns().iter().zip(expected.columns()) {
+            assert_eq!(
+                (0..a.len()).map(|idx| a.get(idx)).collect::<Vec<_>>(),
+                (0..b.len()).map(|idx| b.get(idx)).collect::<Vec<_>>()
+            );
+        }
+
+        assert!(chunk.filter_project(&mask, &[3]).is_err());
+        let short_mask = BoolArray::from_slice(&[Some(true)]);
+        assert!(chunk.filter_project(&short_mask, &[0]).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let chunk = Chunk::new(vec![
+            I32Array::from_slice(&[Some(1), None]).into(),
+            StringArray::from_slice(&[Some("a"), Some("b")]).into(),
+        ]);
+        chunk
+            .validate(&[DataType::Integer, DataType::Varchar])
+            .unwrap();
+
+        let err = chunk
+            .validate(&[DataType::Varchar, DataType::Integer])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "type mismatch at column 0: expect VARCHAR (String), get Int32"
+        );
+
+        assert!(chunk.validate(&[DataType::Integer]).is_err());
+    }
+}
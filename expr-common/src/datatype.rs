@@ -2,6 +2,8 @@
 
 //! Implements logical types for a database system
 
+use crate::array::PhysicalType;
+
 /// Encapsules all supported (logical) data types in the system.
 #[derive(Debug)]
 pub enum DataType {
@@ -15,6 +17,8 @@ pub enum DataType {
     Varchar,
     /// Corresponding to String physical type
     Char { width: u16 },
+    /// Corresponding to Bytes physical type
+    Bytea,
     /// Corresponding to Bool physical type
     Boolean,
     /// Corresponding to Float32 physical type
@@ -23,4 +27,93 @@ pub enum DataType {
     Double,
     /// Corresponding to Decimal physical type
     Decimal { scale: u16, precision: u16 },
+    /// Corresponding to Date32 physical type
+    Date,
+    /// Corresponding to Timestamp physical type
+    Timestamp,
+    /// Corresponding to Json physical type
+    Json,
+    /// Corresponding to Uuid physical type
+    #[cfg(feature = "uuid")]
+    Uuid,
+}
+
+impl DataType {
+    /// Get the physical type that stores values of this logical type.
+    pub fn physical_type(&self) -> PhysicalType {
+        match self {
+            Self::SmallInt => PhysicalType::Int16,
+            Self::Integer => PhysicalType::Int32,
+            Self::BigInt => PhysicalType::Int64,
+            Self::Varchar | Self::Char { .. } => PhysicalType::String,
+            Self::Bytea => PhysicalType::Bytes,
+            Self::Boolean => PhysicalType::Bool,
+            Self::Real => PhysicalType::Float32,
+            Self::Double => PhysicalType::Float64,
+            Self::Decimal { .. } => PhysicalType::Decimal,
+            Self::Date => PhysicalType::Date32,
+            Self::Timestamp => PhysicalType::Timestamp,
+            Self::Json => PhysicalType::Json,
+            #[cfg(feature = "uuid")]
+            Self::Uuid => PhysicalType::Uuid,
+        }
+    }
+}
+
+/// Prints the SQL name of the data type, e.g., `INTEGER` or `DECIMAL(10, 2)`.
+impl std::fmt::Display for DataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SmallInt => write!(f, "SMALLINT"),
+            Self::Integer => write!(f, "INTEGER"),
+            Self::BigInt => write!(f, "BIGINT"),
+            Self::Varchar => write!(f, "VARCHAR"),
+            Self::Char { width } => write!(f, "CHAR({})", width),
+            Self::Bytea => write!(f, "BYTEA"),
+            Self::Boolean => write!(f, "BOOLEAN"),
+            Self::Real => write!(f, "REAL"),
+            Self::Double => write!(f, "DOUBLE"),
+            Self::Decimal { scale, precision } => write!(f, "DECIMAL({}, {})", precision, scale),
+            Self::Date => write!(f, "DATE"),
+            Self::Timestamp => write!(f, "TIMESTAMP"),
+            Self::Json => write!(f, "JSON"),
+            #[cfg(feature = "uuid")]
+            Self::Uuid => write!(f, "UUID"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_physical_type() {
+        let cases = [
+            (DataType::SmallInt, PhysicalType::Int16),
+            (DataType::Integer, PhysicalType::Int32),
+            (DataType::BigInt, PhysicalType::Int64),
+            (DataType::Varchar, PhysicalType::String),
+            (DataType::Char { width: 10 }, PhysicalType::String),
+            (DataType::Bytea, PhysicalType::Bytes),
+            (DataType::Boolean, PhysicalType::Bool),
+            (DataType::Real, PhysicalType::Float32),
+            (DataType::Double, PhysicalType::Float64),
+            (
+                DataType::Decimal {
+                    scale: 2,
+                    precision: 10,
+                },
+                PhysicalType::Decimal,
+            ),
+            (DataType::Date, PhysicalType::Date32),
+            (DataType::Timestamp, PhysicalType::Timestamp),
+            (DataType::Json, PhysicalType::Json),
+            #[cfg(feature = "uuid")]
+            (DataType::Uuid, PhysicalType::Uuid),
+        ];
+        for (datatype, pt) in cases {
+            assert_eq!(datatype.physical_type(), pt, "{}", datatype);
+        }
+    }
 }
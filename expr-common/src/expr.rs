@@ -2,10 +2,78 @@
 
 use anyhow::Result;
 
-use crate::array::ArrayImpl;
+use crate::array::{ArrayBuilderImpl, ArrayImpl, PhysicalType};
+use crate::scalar::{ScalarImpl, ScalarRefImpl};
 
 /// A trait over all expressions -- unary, binary, etc.
 pub trait Expression {
     /// Evaluate an expression with run-time number of [`ArrayImpl`]s.
     fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl>;
 }
+
+/// An input of an expression, which is either an array or a constant of all rows.
+#[derive(Debug, Clone, Copy)]
+pub enum Input<'a> {
+    Array(&'a ArrayImpl),
+    /// The same value for `len` rows, without materializing an array.
+    Constant(Option<ScalarRefImpl<'a>>, usize),
+}
+
+impl<'a> Input<'a> {
+    /// Number of rows of the input.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Array(array) => array.len(),
+            Self::Constant(_, len) => *len,
+        }
+    }
+
+    /// Indicates whether the input has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The output of an expression evaluated on [`Input`]s. The output is a constant if all inputs
+/// are constants.
+#[derive(Debug, Clone)]
+pub enum Output {
+    Array(ArrayImpl),
+    /// The same value for `len` rows, without materializing an array.
+    Constant {
+        value: Option<ScalarImpl>,
+        physical_type: PhysicalType,
+        len: usize,
+    },
+}
+
+impl Output {
+    /// Number of rows of the output.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Array(array) => array.len(),
+            Self::Constant { len, .. } => *len,
+        }
+    }
+
+    /// Indicates whether the output has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Materialize the output as an array.
+    pub fn into_array(self) -> ArrayImpl {
+        match self {
+            Self::Array(array) => array,
+            Self::Constant {
+                value,
+                physical_type,
+                len,
+            } => {
+                let mut builder = ArrayBuilderImpl::with_capacity(physical_type, len);
+                builder.append_n(len, value.as_ref().map(ScalarImpl::as_scalar_ref));
+                builder.finish()
+            }
+        }
+    }
+}
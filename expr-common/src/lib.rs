@@ -12,6 +12,7 @@
 #![feature(trusted_len)]
 
 pub mod array;
+pub mod chunk;
 pub mod datatype;
 pub mod expr;
 mod macros;
@@ -22,6 +23,8 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 #[error("Type mismatch on conversion: expected {0:?}, get {1:?}")]
-pub struct TypeMismatch(PhysicalType, PhysicalType);
+pub struct TypeMismatch(pub PhysicalType, pub PhysicalType);
 
 pub use rust_decimal::Decimal;
+#[cfg(feature = "uuid")]
+pub use uuid::Uuid;
@@ -11,7 +11,8 @@
 /// `{ enum variant name, function suffix name, array type, builder type, scalar type }`
 macro_rules! for_all_variants {
     ($macro:ident $(, $x:ident)*) => {
-        $macro! {
+        $crate::macros::with_feature_variants! {
+            $macro,
             [$($x),*],
             { Int16, int16, I16Array, I16ArrayBuilder, i16, i16 },
             { Int32, int32, I32Array, I32ArrayBuilder, i32, i32 },
@@ -20,8 +21,13 @@ macro_rules! for_all_variants {
             { Float64, float64, F64Array, F64ArrayBuilder, f64, f64 },
             { Bool, bool, BoolArray, BoolArrayBuilder, bool, bool },
             { String, string, StringArray, StringArrayBuilder, String, &'a str },
+            { Bytes, bytes, BytesArray, BytesArrayBuilder, Bytes, BytesRef<'a> },
             { Decimal, decimal, DecimalArray, DecimalArrayBuilder, Decimal, Decimal },
-            { List, list, ListArray, ListArrayBuilder, List, ListRef<'a> }
+            { Date32, date32, DateArray, DateArrayBuilder, Date32, Date32 },
+            { Timestamp, timestamp, TimestampArray, TimestampArrayBuilder, Timestamp, Timestamp },
+            { List, list, ListArray, ListArrayBuilder, List, ListRef<'a> },
+            { Json, json, JsonArray, JsonArrayBuilder, Json, JsonRef<'a> },
+            { Null, null, NullArray, NullArrayBuilder, (), () }
         }
     };
 }
@@ -30,7 +36,8 @@ pub(crate) use for_all_variants;
 
 macro_rules! for_all_primitive_variants {
     ($macro:ident $(, $x:ident)*) => {
-        $macro! {
+        $crate::macros::with_feature_variants! {
+            $macro,
             [$($x),*],
             { Int16, int16, I16Array, I16ArrayBuilder, i16, i16 },
             { Int32, int32, I32Array, I32ArrayBuilder, i32, i32 },
@@ -38,8 +45,38 @@ macro_rules! for_all_primitive_variants {
             { Float32, float32, F32Array, F32ArrayBuilder, f32, f32 },
             { Float64, float64, F64Array, F64ArrayBuilder, f64, f64 },
             { Bool, bool, BoolArray, BoolArrayBuilder, bool, bool },
-            { Decimal, decimal, DecimalArray, DecimalArrayBuilder, Decimal, Decimal }
+            { Decimal, decimal, DecimalArray, DecimalArrayBuilder, Decimal, Decimal },
+            { Date32, date32, DateArray, DateArrayBuilder, Date32, Date32 },
+            { Timestamp, timestamp, TimestampArray, TimestampArrayBuilder, Timestamp, Timestamp }
         }
     };
 }
 pub(crate) use for_all_primitive_variants;
+
+/// Appends variants that are only available with some crate features enabled, and then calls
+/// `$macro` with the full variant list.
+///
+/// All feature-gated variants are primitive ones, so this macro is shared by both
+/// `for_all_variants` and `for_all_primitive_variants`.
+#[cfg(feature = "uuid")]
+macro_rules! with_feature_variants {
+    ($macro:ident, [$($x:ident),*], $($variant:tt),*) => {
+        $macro! {
+            [$($x),*],
+            $($variant,)*
+            { Uuid, uuid, UuidArray, UuidArrayBuilder, Uuid, Uuid }
+        }
+    };
+}
+
+#[cfg(not(feature = "uuid"))]
+macro_rules! with_feature_variants {
+    ($macro:ident, [$($x:ident),*], $($variant:tt),*) => {
+        $macro! {
+            [$($x),*],
+            $($variant),*
+        }
+    };
+}
+
+pub(crate) use with_feature_variants;
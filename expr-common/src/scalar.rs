@@ -6,13 +6,32 @@
 //! owned value of ScalarRef, and ScalarRef is a reference to Scalar. We associate Scalar and
 //! ScalarRef with Array types, and present examples on how to use these traits.
 
+mod arith;
+mod bytes;
+mod checked;
+mod date;
 mod impls;
+mod json;
 mod list;
+mod ord;
+mod timestamp;
 
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+use bytes::DisplayBytes;
+pub use bytes::{Bytes, BytesRef};
+pub use checked::CheckedArith;
+pub use date::*;
+pub use json::*;
 pub use list::*;
+pub use ord::TotalOrd;
 use rust_decimal::Decimal;
+pub use timestamp::*;
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
 
-use crate::array::Array;
+use crate::array::{Array, PhysicalType};
 
 /// An owned single value.
 ///
@@ -28,6 +47,9 @@ where
     /// The corresponding [`ScalarRef`] type.
     type RefType<'a>: ScalarRef<'a, ScalarType = Self, ArrayType = Self::ArrayType>;
 
+    /// The physical type of this scalar.
+    const PHYSICAL_TYPE: PhysicalType;
+
     /// Get a reference of the current value.
     fn as_scalar_ref(&self) -> Self::RefType<'_>;
 
@@ -53,6 +75,7 @@ pub trait ScalarRef<'a>:
 
 /// Encapsules all variants of [`Scalar`]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScalarImpl {
     Int16(i16),
     Int32(i32),
@@ -61,8 +84,15 @@ pub enum ScalarImpl {
     Float64(f64),
     Bool(bool),
     String(String),
+    Bytes(Bytes),
     Decimal(Decimal),
+    Date32(Date32),
+    Timestamp(Timestamp),
     List(List),
+    Json(Json),
+    Null(()),
+    #[cfg(feature = "uuid")]
+    Uuid(Uuid),
 }
 
 impl PartialEq for ScalarImpl {
@@ -72,17 +102,33 @@ impl PartialEq for ScalarImpl {
             (Int16(a), Int16(b)) => a.eq(b),
             (Int32(a), Int32(b)) => a.eq(b),
             (Int64(a), Int64(b)) => a.eq(b),
-            (Float32(a), Float32(b)) => a.eq(b),
-            (Float64(a), Float64(b)) => a.eq(b),
+            (Float32(a), Float32(b)) => TotalOrd::total_cmp(a, b) == Ordering::Equal,
+            (Float64(a), Float64(b)) => TotalOrd::total_cmp(a, b) == Ordering::Equal,
             (Bool(a), Bool(b)) => a.eq(b),
             (String(a), String(b)) => a.eq(b),
+            (Bytes(a), Bytes(b)) => a.eq(b),
             (Decimal(a), Decimal(b)) => a.eq(b),
-            (List(_), List(_)) => unimplemented!("list eq is not implemented"),
+            (Date32(a), Date32(b)) => a.eq(b),
+            (Timestamp(a), Timestamp(b)) => a.eq(b),
+            (List(a), List(b)) => a.as_scalar_ref().eq(&b.as_scalar_ref()),
+            (Json(a), Json(b)) => a.eq(b),
+            (Null(a), Null(b)) => a.eq(b),
+            #[cfg(feature = "uuid")]
+            (Uuid(a), Uuid(b)) => a.eq(b),
             _ => false,
         }
     }
 }
 
+/// Floats are compared with [`TotalOrd`], so `NaN` equals itself and `-0.0` equals `0.0`.
+impl Eq for ScalarImpl {}
+
+impl Hash for ScalarImpl {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_scalar_ref().hash(state)
+    }
+}
+
 /// Encapsules all variants of [`ScalarRef`]
 #[derive(Debug, Clone, Copy)]
 pub enum ScalarRefImpl<'a> {
@@ -93,8 +139,15 @@ pub enum ScalarRefImpl<'a> {
     Float64(f64),
     Bool(bool),
     String(&'a str),
+    Bytes(BytesRef<'a>),
     Decimal(Decimal),
+    Date32(Date32),
+    Timestamp(Timestamp),
     List(ListRef<'a>),
+    Json(JsonRef<'a>),
+    Null(()),
+    #[cfg(feature = "uuid")]
+    Uuid(Uuid),
 }
 
 impl<'a> PartialEq for ScalarRefImpl<'a> {
@@ -104,16 +157,197 @@ impl<'a> PartialEq for ScalarRefImpl<'a> {
             (Int16(a), Int16(b)) => a.eq(b),
             (Int32(a), Int32(b)) => a.eq(b),
             (Int64(a), Int64(b)) => a.eq(b),
-            (Float32(a), Float32(b)) => a.eq(b),
-            (Float64(a), Float64(b)) => a.eq(b),
+            (Float32(a), Float32(b)) => TotalOrd::total_cmp(a, b) == Ordering::Equal,
+            (Float64(a), Float64(b)) => TotalOrd::total_cmp(a, b) == Ordering::Equal,
             (Bool(a), Bool(b)) => a.eq(b),
             (String(a), String(b)) => a.eq(b),
+            (Bytes(a), Bytes(b)) => a.eq(b),
             (Decimal(a), Decimal(b)) => a.eq(b),
-            (List(_), List(_)) => unimplemented!("list eq is not implemented"),
+            (Date32(a), Date32(b)) => a.eq(b),
+            (Timestamp(a), Timestamp(b)) => a.eq(b),
+            (List(a), List(b)) => a.eq(b),
+            (Json(a), Json(b)) => a.eq(b),
+            (Null(a), Null(b)) => a.eq(b),
+            #[cfg(feature = "uuid")]
+            (Uuid(a), Uuid(b)) => a.eq(b),
             _ => false,
         }
     }
 }
+
+/// Floats are compared with [`TotalOrd`], so `NaN` equals itself and `-0.0` equals `0.0`.
+impl<'a> Eq for ScalarRefImpl<'a> {}
+
+/// Prints the value of the scalar, which is the text form of `CAST(x AS VARCHAR)`.
+impl<'a> std::fmt::Display for ScalarRefImpl<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ScalarRefImpl::*;
+        match self {
+            Int16(x) => write!(f, "{}", x),
+            Int32(x) => write!(f, "{}", x),
+            Int64(x) => write!(f, "{}", x),
+            Float32(x) => write!(f, "{}", x),
+            Float64(x) => write!(f, "{}", x),
+            Bool(x) => write!(f, "{}", x),
+            String(x) => write!(f, "{}", x),
+            Bytes(x) => write!(f, "{}", DisplayBytes(x)),
+            Decimal(x) => write!(f, "{}", x),
+            Date32(x) => write!(f, "{}", x),
+            Timestamp(x) => write!(f, "{}", x),
+            List(x) => write!(f, "{}", x),
+            Json(x) => write!(f, "{}", x),
+            Null(_) => write!(f, "NULL"),
+            #[cfg(feature = "uuid")]
+            Uuid(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+/// Scalars of the same variant are ordered by their values. Scalars of different variants are not
+/// comparable.
+impl<'a> PartialOrd for ScalarRefImpl<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        use ScalarRefImpl::*;
+        match (self, other) {
+            (Int16(a), Int16(b)) => a.partial_cmp(b),
+            (Int32(a), Int32(b)) => a.partial_cmp(b),
+            (Int64(a), Int64(b)) => a.partial_cmp(b),
+            (Float32(a), Float32(b)) => a.partial_cmp(b),
+            (Float64(a), Float64(b)) => a.partial_cmp(b),
+            (Bool(a), Bool(b)) => a.partial_cmp(b),
+            (String(a), String(b)) => a.partial_cmp(b),
+            (Bytes(a), Bytes(b)) => a.partial_cmp(b),
+            (Decimal(a), Decimal(b)) => a.partial_cmp(b),
+            (Date32(a), Date32(b)) => a.partial_cmp(b),
+            (Timestamp(a), Timestamp(b)) => a.partial_cmp(b),
+            (List(a), List(b)) => a.partial_cmp(b),
+            (Json(a), Json(b)) => a.partial_cmp(b),
+            (Null(a), Null(b)) => a.partial_cmp(b),
+            #[cfg(feature = "uuid")]
+            (Uuid(a), Uuid(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Hash for ScalarRefImpl<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        use ScalarRefImpl::*;
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Int16(x) => x.hash(state),
+            Int32(x) => x.hash(state),
+            Int64(x) => x.hash(state),
+            // `0.0 == -0.0` and all `NaN`s are equal, so they must have the same hash.
+            Float32(x) => (if x.is_nan() { f32::NAN } else { x + 0.0 })
+                .to_bits()
+                .hash(state),
+            Float64(x) => (if x.is_nan() { f64::NAN } else { x + 0.0 })
+                .to_bits()
+                .hash(state),
+            Bool(x) => x.hash(state),
+            String(x) => x.hash(state),
+            Bytes(x) => x.hash(state),
+            Decimal(x) => x.hash(state),
+            Date32(x) => x.hash(state),
+            Timestamp(x) => x.hash(state),
+            List(x) => x.hash(state),
+            Json(x) => x.hash(state),
+            Null(x) => x.hash(state),
+            #[cfg(feature = "uuid")]
+            Uuid(x) => x.hash(state),
+        }
+    }
+}
+impl<'a> ScalarRefImpl<'a> {
+    /// Write a fixed type tag and an explicit byte encoding of the value to `state`. Different from
+    /// [`Hash`], the bytes depend neither on the `Hash` implementations of the value types nor on
+    /// the order of variants, so they are the same across Rust releases and targets:
+    ///
+    /// * integers, dates and timestamps are written in little-endian;
+    /// * floats are written as the bits of `NaN` for all `NaN`s and of `0.0` for `-0.0`, so that
+    ///   equal values have the same bytes;
+    /// * strings, binary values and JSON texts are prefixed with their length as a `u64`;
+    /// * decimals are normalized, so that `1.0` and `1.00` are the same, and written as the
+    ///   mantissa and the scale;
+    /// * lists are prefixed with their length, and a null element is written as a `0` tag.
+    pub fn hash_stable<H: Hasher>(&self, state: &mut H) {
+        fn write_len_prefixed<H: Hasher>(state: &mut H, bytes: &[u8]) {
+            state.write(&(bytes.len() as u64).to_le_bytes());
+            state.write(bytes);
+        }
+
+        use ScalarRefImpl::*;
+        match self {
+            Int16(x) => {
+                state.write(&[1]);
+                state.write(&x.to_le_bytes());
+            }
+            Int32(x) => {
+                state.write(&[2]);
+                state.write(&x.to_le_bytes());
+            }
+            Int64(x) => {
+                state.write(&[3]);
+                state.write(&x.to_le_bytes());
+            }
+            Float32(x) => {
+                let x = if x.is_nan() { f32::NAN } else { x + 0.0 };
+                state.write(&[4]);
+                state.write(&x.to_bits().to_le_bytes());
+            }
+            Float64(x) => {
+                let x = if x.is_nan() { f64::NAN } else { x + 0.0 };
+                state.write(&[5]);
+                state.write(&x.to_bits().to_le_bytes());
+            }
+            Bool(x) => state.write(&[6, *x as u8]),
+            String(x) => {
+                state.write(&[7]);
+                write_len_prefixed(state, x.as_bytes());
+            }
+            Bytes(x) => {
+                state.write(&[8]);
+                write_len_prefixed(state, x);
+            }
+            Decimal(x) => {
+                let x = x.normalize();
+                state.write(&[9]);
+                state.write(&x.mantissa().to_le_bytes());
+                state.write(&x.scale().to_le_bytes());
+            }
+            Date32(x) => {
+                state.write(&[10]);
+                state.write(&x.0.to_le_bytes());
+            }
+            Timestamp(x) => {
+                state.write(&[11]);
+                state.write(&x.0.to_le_bytes());
+            }
+            List(x) => {
+                state.write(&[12]);
+                state.write(&(x.len() as u64).to_le_bytes());
+                for idx in 0..x.len() {
+                    match x.get(idx) {
+                        Some(value) => value.hash_stable(state),
+                        None => state.write(&[0]),
+                    }
+                }
+            }
+            Json(x) => {
+                state.write(&[13]);
+                write_len_prefixed(state, x.as_str().as_bytes());
+            }
+            Null(()) => state.write(&[14]),
+            #[cfg(feature = "uuid")]
+            Uuid(x) => {
+                state.write(&[15]);
+                state.write(x.as_bytes());
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +411,53 @@ mod tests {
         assert_eq!(i1, i);
         assert_eq!(i2, i);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        fn round_trip(scalar: ScalarImpl) {
+            let json = serde_json::to_string(&scalar).unwrap();
+            let de: ScalarImpl = serde_json::from_str(&json).unwrap();
+            assert_eq!(de, scalar, "{}", json);
+        }
+
+        round_trip(ScalarImpl::Int16(-1));
+        round_trip(ScalarImpl::Int32(2333));
+        round_trip(ScalarImpl::Int64(i64::MAX));
+        round_trip(ScalarImpl::Float32(1.5));
+        round_trip(ScalarImpl::Float64(-0.25));
+        round_trip(ScalarImpl::Bool(true));
+        round_trip(ScalarImpl::String("233".to_string()));
+        round_trip(ScalarImpl::Bytes(vec![0, 255]));
+        round_trip(ScalarImpl::Decimal("-12.345".parse().unwrap()));
+        round_trip(ScalarImpl::Date32(Date32::from_ymd(2022, 3, 9).unwrap()));
+        round_trip(ScalarImpl::Timestamp(Timestamp(1_646_829_296_000_000)));
+        round_trip(ScalarImpl::Json(
+            Json::parse(r#"{"a": [1, null]}"#.to_string()).unwrap(),
+        ));
+        round_trip(ScalarImpl::Null(()));
+        #[cfg(feature = "uuid")]
+        round_trip(ScalarImpl::Uuid(Uuid::from_u128(0x1234_5678_9abc_def0)));
+
+        // A list of lists, where the inner lists are of `Int32`.
+        let inner1: ArrayImpl = I32Array::from_slice(&[Some(1), None]).into();
+        let inner1 = inner1.into_boxed_array();
+        let inner2: ArrayImpl = I32Array::from_slice(&[]).into();
+        let inner2 = inner2.into_boxed_array();
+        let mut builder = ListArrayBuilder::with_capacity(3);
+        builder.push(Some((&inner1).into()));
+        builder.push(None);
+        builder.push(Some((&inner2).into()));
+        let outer: ArrayImpl = builder.finish().into();
+        let outer = outer.into_boxed_array();
+        round_trip(ScalarImpl::List(ListRef::from(&outer).to_owned_scalar()));
+
+        // JSON texts are only validated with the `serde_json` feature.
+        #[cfg(feature = "serde_json")]
+        assert!(serde_json::from_str::<ScalarImpl>(r#"{"Json":"{"}"#).is_err());
+        assert!(serde_json::from_str::<ScalarImpl>(
+            r#"{"List":{"type":"Int32","items":[{"String":"a"}]}}"#
+        )
+        .is_err());
+    }
 }
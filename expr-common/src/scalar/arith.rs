@@ -0,0 +1,272 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Contains arithmetic over [`ScalarRefImpl`]
+//!
+//! These functions dispatch on the variants of both operands at runtime, so that a tree-walking
+//! interpreter can evaluate expressions without monomorphizing on the scalar types. Operands are
+//! widened to a common type before the operator is applied, in the order of `Int16 < Int32 <
+//! Int64 < Decimal < Float32 < Float64`.
+
+use anyhow::{anyhow, Result};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use super::{ScalarImpl, ScalarRefImpl};
+use crate::array::PhysicalType;
+
+/// Two numeric operands widened to the same type.
+enum Widened {
+    Int16(i16, i16),
+    Int32(i32, i32),
+    Int64(i64, i64),
+    Decimal(Decimal, Decimal),
+    Float32(f32, f32),
+    Float64(f64, f64),
+}
+
+impl PhysicalType {
+    /// Rank of a numeric type in the widening order. Returns `None` for non-numeric types.
+    fn numeric_rank(self) -> Option<u8> {
+        match self {
+            Self::Int16 => Some(0),
+            Self::Int32 => Some(1),
+            Self::Int64 => Some(2),
+            Self::Decimal => Some(3),
+            Self::Float32 => Some(4),
+            Self::Float64 => Some(5),
+            _ => None,
+        }
+    }
+
+    /// Get the narrowest numeric type that both types can be widened to. Returns `None` if either
+    /// of them is not numeric.
+    pub fn common_numeric_type(self, other: Self) -> Option<Self> {
+        match (self.numeric_rank()?, other.numeric_rank()?) {
+            (a, b) if a >= b => Some(self),
+            _ => Some(other),
+        }
+    }
+}
+
+impl<'a> ScalarRefImpl<'a> {
+    fn widen_to_i32(&self) -> i32 {
+        match *self {
+            Self::Int16(x) => x.into(),
+            Self::Int32(x) => x,
+            _ => unreachable!(),
+        }
+    }
+
+    fn widen_to_i64(&self) -> i64 {
+        match *self {
+            Self::Int64(x) => x,
+            _ => self.widen_to_i32().into(),
+        }
+    }
+
+    fn widen_to_decimal(&self) -> Decimal {
+        match *self {
+            Self::Decimal(x) => x,
+            _ => self.widen_to_i64().into(),
+        }
+    }
+
+    fn widen_to_f64(&self) -> f64 {
+        match *self {
+            Self::Float32(x) => x.into(),
+            Self::Float64(x) => x,
+            Self::Decimal(x) => x.to_f64().unwrap(),
+            _ => self.widen_to_i64() as f64,
+        }
+    }
+
+    fn widen_to_f32(&self) -> f32 {
+        match *self {
+            Self::Float32(x) => x,
+            Self::Decimal(x) => x.to_f32().unwrap(),
+            _ => self.widen_to_i64() as f32,
+        }
+    }
+
+    fn widen_to_i16(&self) -> i16 {
+        match *self {
+            Self::Int16(x) => x,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Widen the scalar to a numeric type, which must be at least as wide as the scalar's own
+    /// type in the widening order.
+    pub fn widen_to(&self, ty: PhysicalType) -> Result<ScalarImpl> {
+        if self.physical_type().common_numeric_type(ty) != Some(ty) {
+            return Err(anyhow!("cannot widen {} to {}", self.physical_type(), ty));
+        }
+        let widened = match ty {
+            PhysicalType::Int16 => ScalarImpl::Int16(self.widen_to_i16()),
+            PhysicalType::Int32 => ScalarImpl::Int32(self.widen_to_i32()),
+            PhysicalType::Int64 => ScalarImpl::Int64(self.widen_to_i64()),
+            PhysicalType::Decimal => ScalarImpl::Decimal(self.widen_to_decimal()),
+            PhysicalType::Float32 => ScalarImpl::Float32(self.widen_to_f32()),
+            PhysicalType::Float64 => ScalarImpl::Float64(self.widen_to_f64()),
+            _ => unreachable!(),
+        };
+        Ok(widened)
+    }
+
+    /// Widen both operands to their common numeric type.
+    fn widen(&self, other: &Self, op: &str) -> Result<Widened> {
+        let ty = self
+            .physical_type()
+            .common_numeric_type(other.physical_type())
+            .ok_or_else(|| {
+                anyhow!(
+                    "cannot apply {} to {} and {}",
+                    op,
+                    self.physical_type(),
+                    other.physical_type()
+                )
+            })?;
+        let widened = match ty {
+            PhysicalType::Int16 => Widened::Int16(self.widen_to_i16(), other.widen_to_i16()),
+            PhysicalType::Int32 => Widened::Int32(self.widen_to_i32(), other.widen_to_i32()),
+            PhysicalType::Int64 => Widened::Int64(self.widen_to_i64(), other.widen_to_i64()),
+            PhysicalType::Decimal => {
+                Widened::Decimal(self.widen_to_decimal(), other.widen_to_decimal())
+            }
+            PhysicalType::Float32 => Widened::Float32(self.widen_to_f32(), other.widen_to_f32()),
+            PhysicalType::Float64 => Widened::Float64(self.widen_to_f64(), other.widen_to_f64()),
+            _ => unreachable!(),
+        };
+        Ok(widened)
+    }
+}
+
+/// Implements arithmetic methods on [`ScalarRefImpl`]. Integers and decimals use the checked
+/// operations, and floats follow IEEE 754.
+macro_rules! impl_scalar_ref_arith {
+    ($( { $op:ident, $checked:ident, $float_op:tt } ),*) => {
+        impl<'a> ScalarRefImpl<'a> {
+            $(
+                #[doc = concat!(
+                    "Apply `", stringify!($op), "` on two scalars, widening them to a common type. ",
+                    "Returns an error on incompatible types, overflow or division by zero.")]
+                pub fn $op(&self, other: &ScalarRefImpl) -> Result<ScalarImpl> {
+                    let result = match self.widen(other, stringify!($op))? {
+                        Widened::Int16(a, b) => a.$checked(b).map(ScalarImpl::Int16),
+                        Widened::Int32(a, b) => a.$checked(b).map(ScalarImpl::Int32),
+                        Widened::Int64(a, b) => a.$checked(b).map(ScalarImpl::Int64),
+                        Widened::Decimal(a, b) => a.$checked(b).map(ScalarImpl::Decimal),
+                        Widened::Float32(a, b) => Some(ScalarImpl::Float32(a $float_op b)),
+                        Widened::Float64(a, b) => Some(ScalarImpl::Float64(a $float_op b)),
+                    };
+                    result.ok_or_else(|| {
+                        anyhow!(
+                            "overflow or division by zero: {:?} {} {:?}",
+                            self,
+                            stringify!($op),
+                            other
+                        )
+                    })
+                }
+            )*
+        }
+    };
+}
+
+impl_scalar_ref_arith! {
+    { add, checked_add, + },
+    { sub, checked_sub, - },
+    { mul, checked_mul, * },
+    { div, checked_div, / }
+}
+
+impl ScalarImpl {
+    /// Add two owned scalars. See [`ScalarRefImpl::add`].
+    ///
+    /// Returns an error on integer or decimal overflow instead of wrapping, which is the same as
+    /// the vectorized arithmetic functions.
+    pub fn checked_add(&self, other: &ScalarImpl) -> Result<ScalarImpl> {
+        self.as_scalar_ref().add(&other.as_scalar_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_widening() {
+        let result = ScalarRefImpl::Int32(1)
+            .add(&ScalarRefImpl::Int64(2))
+            .unwrap();
+        assert_eq!(result, ScalarImpl::Int64(3));
+        let result = ScalarRefImpl::Int16(1)
+            .add(&ScalarRefImpl::Int16(2))
+            .unwrap();
+        assert_eq!(result, ScalarImpl::Int16(3));
+        let result = ScalarRefImpl::Int32(1)
+            .mul(&ScalarRefImpl::Float64(1.5))
+            .unwrap();
+        assert_eq!(result, ScalarImpl::Float64(1.5));
+        let result = ScalarRefImpl::Decimal(Decimal::new(15, 1))
+            .sub(&ScalarRefImpl::Int32(1))
+            .unwrap();
+        assert_eq!(result, ScalarImpl::Decimal(Decimal::new(5, 1)));
+    }
+
+    #[test]
+    fn test_checked_add() {
+        assert_eq!(
+            ScalarImpl::Int16(1)
+                .checked_add(&ScalarImpl::Int16(2))
+                .unwrap(),
+            ScalarImpl::Int16(3)
+        );
+        assert!(ScalarImpl::Int16(i16::MAX)
+            .checked_add(&ScalarImpl::Int16(1))
+            .is_err());
+        assert!(ScalarImpl::Decimal(Decimal::MAX)
+            .checked_add(&ScalarImpl::Int32(1))
+            .is_err());
+    }
+
+    #[test]
+    fn test_widen_to() {
+        assert_eq!(
+            PhysicalType::Int16.common_numeric_type(PhysicalType::Int32),
+            Some(PhysicalType::Int32)
+        );
+        assert_eq!(
+            PhysicalType::String.common_numeric_type(PhysicalType::Int32),
+            None
+        );
+        assert_eq!(
+            ScalarRefImpl::Int16(1)
+                .widen_to(PhysicalType::Float64)
+                .unwrap(),
+            ScalarImpl::Float64(1.0)
+        );
+        assert!(ScalarRefImpl::Int64(1)
+            .widen_to(PhysicalType::Int32)
+            .is_err());
+    }
+
+    #[test]
+    fn test_arith_error() {
+        assert!(ScalarRefImpl::String("1")
+            .add(&ScalarRefImpl::Int32(1))
+            .is_err());
+        assert!(ScalarRefImpl::Int32(1)
+            .div(&ScalarRefImpl::Int64(0))
+            .is_err());
+        assert!(ScalarRefImpl::Int16(i16::MAX)
+            .add(&ScalarRefImpl::Int16(1))
+            .is_err());
+        assert_eq!(
+            ScalarRefImpl::Int32(i16::MAX as i32)
+                .add(&ScalarRefImpl::Int16(1))
+                .unwrap(),
+            ScalarImpl::Int32(i16::MAX as i32 + 1)
+        );
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+use super::{Scalar, ScalarRef};
+use crate::array::{BytesArray, PhysicalType};
+
+/// An owned binary value, which may not be valid UTF-8.
+pub type Bytes = Vec<u8>;
+
+/// A reference to a binary value.
+pub type BytesRef<'a> = &'a [u8];
+
+/// Implement [`Scalar`] for `Vec<u8>`.
+impl Scalar for Bytes {
+    type ArrayType = BytesArray;
+    type RefType<'a> = BytesRef<'a>;
+
+    const PHYSICAL_TYPE: PhysicalType = PhysicalType::Bytes;
+
+    fn as_scalar_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+
+    fn upcast_gat<'short, 'long: 'short>(long: &'long [u8]) -> &'short [u8] {
+        long
+    }
+}
+
+/// Implement [`ScalarRef`] for `&[u8]`.
+impl<'a> ScalarRef<'a> for BytesRef<'a> {
+    type ArrayType = BytesArray;
+    type ScalarType = Bytes;
+
+    fn to_owned_scalar(&self) -> Bytes {
+        self.to_vec()
+    }
+}
+
+/// Display binary values in the hex format of PostgreSQL, e.g., `\xff00`.
+pub(crate) struct DisplayBytes<'a>(pub BytesRef<'a>);
+
+impl std::fmt::Display for DisplayBytes<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\\x")?;
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Checked arithmetic over primitive scalars
+
+use rust_decimal::Decimal;
+
+/// Arithmetic operations that return `None` on overflow instead of wrapping or panicking.
+///
+/// Floats never overflow, and follow IEEE 754 instead.
+pub trait CheckedArith: Sized {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_arith {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedArith for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_div(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+// `Decimal` rescales both operands to the larger scale before adding or subtracting, so the
+// result keeps all digits of both operands, e.g., `1.0 + 0.25 = 1.25`.
+impl_checked_arith! { i16, i32, i64, Decimal }
+
+macro_rules! impl_float_checked_arith {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedArith for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    Some(self + rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    Some(self - rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    Some(self * rhs)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    Some(self / rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_float_checked_arith! { f32, f64 }
@@ -0,0 +1,86 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+use std::fmt::{Debug, Display};
+
+/// A date stored as the number of days since `1970-01-01`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Date32(pub i32);
+
+impl Date32 {
+    /// Create a date from year, month and day. Returns `None` if the date doesn't exist.
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        // See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+        let y = if month <= 2 { year - 1 } else { year } as i64;
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let mp = (month as i64 + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        i32::try_from(era * 146097 + doe - 719468).ok().map(Self)
+    }
+
+    /// Get year, month and day of the date.
+    pub fn to_ymd(self) -> (i32, u32, u32) {
+        // See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+        let z = self.0 as i64 + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + (month <= 2) as i64) as i32;
+        (year, month, day)
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Prints the date as `YYYY-MM-DD`.
+impl Display for Date32 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day) = self.to_ymd();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+impl Debug for Date32 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_ymd() {
+        assert_eq!(Date32::from_ymd(1970, 1, 1), Some(Date32(0)));
+        assert_eq!(Date32::from_ymd(2000, 3, 1), Some(Date32(11017)));
+        assert_eq!(Date32::from_ymd(1969, 12, 31), Some(Date32(-1)));
+        assert_eq!(Date32::from_ymd(2021, 2, 29), None);
+        assert_eq!(Date32(11017).to_ymd(), (2000, 3, 1));
+        assert_eq!(
+            Date32::from_ymd(2024, 2, 29).unwrap().to_string(),
+            "2024-02-29"
+        );
+        assert_eq!(format!("{:?}", Date32(-1)), "1969-12-31");
+    }
+}
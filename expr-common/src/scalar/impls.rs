@@ -5,6 +5,8 @@
 //! Contains all macro-generated implementations of scalar methods
 
 use rust_decimal::Decimal;
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
 
 use crate::array::*;
 use crate::macros::{for_all_primitive_variants, for_all_variants};
@@ -23,6 +25,25 @@ macro_rules! impl_scalar_dispatch {
                     )*
                 }
             }
+
+            /// Get a reference of the current scalar
+            pub fn as_scalar_ref(&self) -> ScalarRefImpl<'_> {
+                match self {
+                    $(
+                        Self::$Abc(v) => ScalarRefImpl::$Abc(v.as_scalar_ref()),
+                    )*
+                }
+            }
+
+            /// Build an owned scalar from a reference. For `List`, the referenced range is copied
+            /// into a new list.
+            pub fn from_ref(r: ScalarRefImpl<'_>) -> ScalarImpl {
+                match r {
+                    $(
+                        ScalarRefImpl::$Abc(v) => Self::$Abc(v.to_owned_scalar()),
+                    )*
+                }
+            }
         }
     }
 }
@@ -105,6 +126,8 @@ macro_rules! impl_scalar {
                 type ArrayType = $AbcArray;
                 type RefType<'a> = $Owned;
 
+                const PHYSICAL_TYPE: PhysicalType = PhysicalType::$Abc;
+
                 fn as_scalar_ref(&self) -> $Owned {
                     *self
                 }
@@ -136,6 +159,8 @@ impl Scalar for String {
     type ArrayType = StringArray;
     type RefType<'a> = &'a str;
 
+    const PHYSICAL_TYPE: PhysicalType = PhysicalType::String;
+
     fn as_scalar_ref(&self) -> &str {
         self.as_str()
     }
@@ -154,3 +179,105 @@ impl<'a> ScalarRef<'a> for &'a str {
         self.to_string()
     }
 }
+
+/// Implement [`Scalar`] for `()`, which is the element type of [`NullArray`]. As all elements of
+/// a [`NullArray`] are null, this is never actually read from an array.
+impl Scalar for () {
+    type ArrayType = NullArray;
+    type RefType<'a> = ();
+
+    const PHYSICAL_TYPE: PhysicalType = PhysicalType::Null;
+
+    fn as_scalar_ref(&self) {}
+
+    fn upcast_gat<'short, 'long: 'short>(long: Self::RefType<'long>) -> Self::RefType<'short> {
+        long
+    }
+}
+
+/// Implement [`ScalarRef`] for `()`.
+impl<'a> ScalarRef<'a> for () {
+    type ArrayType = NullArray;
+    type ScalarType = ();
+
+    fn to_owned_scalar(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_ref() {
+        let refs = [
+            ScalarRefImpl::Int16(1),
+            ScalarRefImpl::Int32(2),
+            ScalarRefImpl::Int64(3),
+            ScalarRefImpl::Float32(4.0),
+            ScalarRefImpl::Float64(5.0),
+            ScalarRefImpl::Bool(true),
+            ScalarRefImpl::String("abc"),
+            ScalarRefImpl::Bytes(&[0xff, 0x00]),
+            ScalarRefImpl::Decimal(Decimal::new(123, 2)),
+            ScalarRefImpl::Json(JsonRef::parse("[1]").unwrap()),
+        ];
+        for r in refs {
+            assert_eq!(ScalarImpl::from_ref(r).as_scalar_ref(), r);
+        }
+    }
+
+    #[test]
+    fn test_from_ref_list() {
+        let array: ArrayImpl = I32Array::from_slice(&[Some(0), Some(1), None, Some(3)]).into();
+        let array = array.into_boxed_array();
+        let list_ref: ListRef = (&array).into();
+        let sliced = list_ref.slice(1..3);
+        let owned = ScalarImpl::from_ref(ScalarRefImpl::List(sliced));
+        match &owned {
+            ScalarImpl::List(list) => {
+                assert_eq!(list.len(), 2);
+                assert_eq!(list.get(0), Some(ScalarRefImpl::Int32(1)));
+                assert_eq!(list.get(1), None);
+            }
+            other => panic!("expect list, get {:?}", other),
+        }
+        assert_eq!(owned.as_scalar_ref(), ScalarRefImpl::List(sliced));
+    }
+
+    #[test]
+    fn test_physical_type() {
+        let array: ArrayImpl = I32Array::from_slice(&[Some(0)]).into();
+        let array = array.into_boxed_array();
+        let cases = [
+            (ScalarRefImpl::Int16(1), PhysicalType::Int16),
+            (ScalarRefImpl::Int32(2), PhysicalType::Int32),
+            (ScalarRefImpl::Int64(3), PhysicalType::Int64),
+            (ScalarRefImpl::Float32(4.0), PhysicalType::Float32),
+            (ScalarRefImpl::Float64(5.0), PhysicalType::Float64),
+            (ScalarRefImpl::Bool(true), PhysicalType::Bool),
+            (ScalarRefImpl::String("abc"), PhysicalType::String),
+            (ScalarRefImpl::Bytes(&[0xff]), PhysicalType::Bytes),
+            (
+                ScalarRefImpl::Decimal(Decimal::new(123, 2)),
+                PhysicalType::Decimal,
+            ),
+            (ScalarRefImpl::Date32(Date32(1)), PhysicalType::Date32),
+            (
+                ScalarRefImpl::Timestamp(Timestamp(1)),
+                PhysicalType::Timestamp,
+            ),
+            (ScalarRefImpl::List((&array).into()), PhysicalType::List),
+            (
+                ScalarRefImpl::Json(JsonRef::parse("[1]").unwrap()),
+                PhysicalType::Json,
+            ),
+            (ScalarRefImpl::Null(()), PhysicalType::Null),
+            #[cfg(feature = "uuid")]
+            (ScalarRefImpl::Uuid(Uuid::nil()), PhysicalType::Uuid),
+        ];
+        for (r, pt) in cases {
+            assert_eq!(r.physical_type(), pt);
+            assert_eq!(ScalarImpl::from_ref(r).physical_type(), pt);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+use thiserror::Error;
+
+use super::{Scalar, ScalarRef};
+use crate::array::{JsonArray, PhysicalType};
+
+/// Error of parsing an invalid JSON text.
+#[derive(Error, Debug)]
+#[error("invalid JSON: {0}")]
+pub struct InvalidJson(pub String);
+
+/// Check if `s` is a valid JSON text.
+#[cfg(feature = "serde_json")]
+fn validate_json(s: &str) -> Result<(), InvalidJson> {
+    serde_json::from_str::<serde_json::Value>(s)
+        .map(|_| ())
+        .map_err(|e| InvalidJson(e.to_string()))
+}
+
+/// Without the `serde_json` feature, all texts are accepted as JSON.
+#[cfg(not(feature = "serde_json"))]
+fn validate_json(_: &str) -> Result<(), InvalidJson> {
+    Ok(())
+}
+
+/// A JSON value stored as its text.
+///
+/// [`Json`] is physically a string, but is a different type from [`String`], so that operators
+/// can apply JSON semantics on it.
+///
+/// With the `serde` feature, [`Json`] is serialized as its text, and the text is validated when
+/// deserializing.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String")
+)]
+pub struct Json(String);
+
+/// A reference to the text of a JSON value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JsonRef<'a>(&'a str);
+
+impl Json {
+    /// Create a [`Json`] from a JSON text, returning an error if the text is not valid.
+    pub fn parse(s: String) -> Result<Self, InvalidJson> {
+        validate_json(&s)?;
+        Ok(Self(s))
+    }
+
+    /// Get the JSON text.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for Json {
+    type Error = InvalidJson;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(s)
+    }
+}
+
+impl<'a> JsonRef<'a> {
+    /// Create a [`JsonRef`] from a JSON text, returning an error if the text is not valid.
+    pub fn parse(s: &'a str) -> Result<Self, InvalidJson> {
+        validate_json(s)?;
+        Ok(Self(s))
+    }
+
+    /// Create a [`JsonRef`] from a text that is known to be valid, e.g., read from a
+    /// [`JsonArray`].
+    pub(crate) fn new_unchecked(s: &'a str) -> Self {
+        Self(s)
+    }
+
+    /// Get the JSON text.
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+}
+
+/// Implement [`Scalar`] for `Json`.
+impl Scalar for Json {
+    type ArrayType = JsonArray;
+    type RefType<'a> = JsonRef<'a>;
+
+    const PHYSICAL_TYPE: PhysicalType = PhysicalType::Json;
+
+    fn as_scalar_ref(&self) -> JsonRef<'_> {
+        JsonRef(&self.0)
+    }
+
+    fn upcast_gat<'short, 'long: 'short>(long: JsonRef<'long>) -> JsonRef<'short> {
+        long
+    }
+}
+
+/// Implement [`ScalarRef`] for `JsonRef<'a>`.
+impl<'a> ScalarRef<'a> for JsonRef<'a> {
+    type ArrayType = JsonArray;
+    type ScalarType = Json;
+
+    fn to_owned_scalar(&self) -> Json {
+        Json(self.0.to_string())
+    }
+}
+
+impl<'a> std::fmt::Display for JsonRef<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json() {
+        assert_eq!(
+            JsonRef::parse(r#"{"a": [1, null]}"#).unwrap().as_str(),
+            r#"{"a": [1, null]}"#
+        );
+        assert!(Json::parse("1".to_string()).is_ok());
+        assert!(JsonRef::parse(r#"{"a": "#).is_err());
+        assert!(Json::parse("abc".to_string()).is_err());
+    }
+}
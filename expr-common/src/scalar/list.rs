@@ -1,11 +1,18 @@
 // Copyright 2022 Alex Chi. Licensed under Apache-2.0.
 
+use std::cmp::Ordering;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 use std::ops::{Bound, RangeBounds};
 
+#[cfg(feature = "serde")]
+use super::ScalarImpl;
 use super::{Array, Scalar, ScalarRef, ScalarRefImpl};
-use crate::array::{ArrayImplRef, BoxedArray, ListArray};
+#[cfg(feature = "serde")]
+use crate::array::ArrayBuilderImpl;
+use crate::array::{ArrayImpl, ArrayImplRef, BoxedArray, ListArray, PhysicalType};
 use crate::macros::for_all_variants;
+use crate::TypeMismatch;
 
 #[derive(Clone, Debug)]
 pub struct List(BoxedArray);
@@ -59,6 +66,8 @@ impl Scalar for List {
     type ArrayType = ListArray;
     type RefType<'a> = ListRef<'a>;
 
+    const PHYSICAL_TYPE: PhysicalType = PhysicalType::List;
+
     fn as_scalar_ref(&self) -> ListRef<'_> {
         ListRef {
             array: &self.0,
@@ -86,6 +95,56 @@ impl List {
     }
 }
 
+/// Create a [`List`] of all elements in the array.
+impl From<ArrayImpl> for List {
+    fn from(array: ArrayImpl) -> Self {
+        List(array.into_boxed_array())
+    }
+}
+
+/// A list is serialized together with the physical type of its elements, so that empty lists and
+/// lists of nulls can be deserialized, e.g., `{"type":"Int32","items":[{"Int32":1},null]}`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for List {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let items = (0..self.len())
+            .map(|idx| self.get(idx).map(ScalarImpl::from_ref))
+            .collect::<Vec<_>>();
+        let mut s = serializer.serialize_struct("List", 2)?;
+        s.serialize_field("type", &self.0.physical_type().to_string())?;
+        s.serialize_field("items", &items)?;
+        s.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for List {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        struct ListRepr {
+            r#type: String,
+            items: Vec<Option<ScalarImpl>>,
+        }
+
+        let repr = ListRepr::deserialize(deserializer)?;
+        let ty: PhysicalType = repr.r#type.parse().map_err(D::Error::custom)?;
+        let mut builder = ArrayBuilderImpl::with_capacity(ty, repr.items.len());
+        for item in &repr.items {
+            if let Some(item) = item {
+                if item.physical_type() != ty {
+                    return Err(D::Error::custom(TypeMismatch(ty, item.physical_type())));
+                }
+            }
+            builder.push(item.as_ref().map(ScalarImpl::as_scalar_ref));
+        }
+        Ok(List(builder.finish().into_boxed_array()))
+    }
+}
+
 /// Implement [`ScalarRef`] for `ListRef<'a>`.
 impl<'a> ScalarRef<'a> for ListRef<'a> {
     type ArrayType = ListArray;
@@ -100,6 +159,52 @@ impl<'a> ScalarRef<'a> for ListRef<'a> {
     }
 }
 
+/// Prints the list as `[1, NULL, 3]`.
+impl<'a> std::fmt::Display for ListRef<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for idx in 0..self.len() {
+            if idx != 0 {
+                write!(f, ", ")?;
+            }
+            match self.get(idx) {
+                Some(x) => write!(f, "{}", x)?,
+                None => write!(f, "NULL")?,
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+/// Two lists are equal if they have the same length and equal elements at every position.
+impl<'a> PartialEq for ListRef<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && (0..self.len()).all(|idx| self.get(idx) == other.get(idx))
+    }
+}
+
+/// Lists are compared lexicographically, where null elements are smaller than non-null ones.
+impl<'a> PartialOrd for ListRef<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        for idx in 0..self.len().min(other.len()) {
+            match self.get(idx).partial_cmp(&other.get(idx))? {
+                Ordering::Equal => {}
+                ord => return Some(ord),
+            }
+        }
+        Some(self.len().cmp(&other.len()))
+    }
+}
+
+impl<'a> Hash for ListRef<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for idx in 0..self.len() {
+            self.get(idx).hash(state);
+        }
+    }
+}
+
 impl<'a> ListRef<'a> {
     /// Get length of [`List`]
     pub fn len(&self) -> usize {
@@ -118,6 +223,24 @@ impl<'a> ListRef<'a> {
         self.array.get(idx + self.offset.0)
     }
 
+    /// Concatenate two lists into a new list. Both lists must have the same element type.
+    pub fn concat(&self, other: &ListRef<'_>) -> Result<List, TypeMismatch> {
+        if self.array.physical_type() != other.array.physical_type() {
+            return Err(TypeMismatch(
+                self.array.physical_type(),
+                other.array.physical_type(),
+            ));
+        }
+        let mut builder = self.array.new_builder(self.len() + other.len());
+        for idx in 0..self.len() {
+            builder.push(self.get(idx));
+        }
+        for idx in 0..other.len() {
+            builder.push(other.get(idx));
+        }
+        Ok(List(builder.finish().into_boxed_array()))
+    }
+
     fn slice_from_to(&self, from: usize, to: usize) -> Self {
         assert!(to <= self.offset.1);
         assert!(from >= self.offset.0);
@@ -147,6 +270,7 @@ impl<'a> ListRef<'a> {
 mod tests {
     use super::*;
     use crate::array::{Array, ArrayImpl, I32Array};
+    use crate::scalar::ScalarImpl;
 
     #[test]
     fn test_list_debug() {
@@ -164,4 +288,18 @@ mod tests {
         assert_eq!(format!("{:?}", list_ref.slice(..=0)), "[Some(0)]");
         assert_eq!(format!("{:?}", list_ref.slice(1..=2)), "[Some(1), None]");
     }
+
+    #[test]
+    fn test_list_eq() {
+        let x: ArrayImpl = I32Array::from_slice(&[Some(0), None, Some(0), None]).into();
+        let x = x.into_boxed_array();
+        let list_ref: ListRef = (&x).into();
+        assert_eq!(list_ref.slice(..2), list_ref.slice(2..));
+        assert_ne!(list_ref.slice(..2), list_ref.slice(1..3));
+        assert_ne!(list_ref.slice(..2), list_ref.slice(..1));
+        assert_eq!(
+            ScalarImpl::List(list_ref.slice(..2).to_owned_scalar()),
+            ScalarImpl::List(list_ref.slice(2..).to_owned_scalar())
+        );
+    }
 }
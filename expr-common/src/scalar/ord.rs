@@ -0,0 +1,98 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Total order over scalars
+
+use std::cmp::Ordering;
+
+use rust_decimal::Decimal;
+
+use super::{ListRef, ScalarRefImpl};
+
+/// A total order, so that comparing and sorting any two values never panics and never depends on
+/// the order of comparisons. `NaN` is greater than all other floats and equal to itself.
+pub trait TotalOrd {
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+/// Implements [`TotalOrd`] for types that are already [`Ord`].
+macro_rules! impl_total_ord {
+    ($($t:ty),*) => {
+        $(
+            impl TotalOrd for $t {
+                fn total_cmp(&self, other: &Self) -> Ordering {
+                    Ord::cmp(self, other)
+                }
+            }
+        )*
+    };
+}
+
+// `Decimal` is compared by value regardless of scale, so `1.0` equals `1.00`.
+impl_total_ord! { i16, i32, i64, Decimal, &str }
+
+/// Implements [`TotalOrd`] for floats. `NaN` is greater than all other values and equal to
+/// itself, regardless of its sign. Different from `f64::total_cmp`, `-0.0` still equals `0.0`,
+/// so the result is unchanged for values that are not `NaN`.
+macro_rules! impl_float_total_ord {
+    ($($t:ty),*) => {
+        $(
+            impl TotalOrd for $t {
+                fn total_cmp(&self, other: &Self) -> Ordering {
+                    self.partial_cmp(other)
+                        .unwrap_or_else(|| self.is_nan().cmp(&other.is_nan()))
+                }
+            }
+        )*
+    };
+}
+
+impl_float_total_ord! { f32, f64 }
+
+/// Same as [`PartialOrd`] of [`ListRef`], except that elements are compared with [`TotalOrd`].
+impl<'a> TotalOrd for ListRef<'a> {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        for idx in 0..self.len().min(other.len()) {
+            let ord = match (self.get(idx), other.get(idx)) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (a, b) => a.is_some().cmp(&b.is_some()),
+            };
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+        self.len().cmp(&other.len())
+    }
+}
+
+/// Same as [`PartialOrd`] of [`ScalarRefImpl`], except that floats and lists are compared with
+/// [`TotalOrd`]. Scalars of different variants are treated as equal.
+impl<'a> TotalOrd for ScalarRefImpl<'a> {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        use ScalarRefImpl::*;
+        match (self, other) {
+            (Float32(a), Float32(b)) => TotalOrd::total_cmp(a, b),
+            (Float64(a), Float64(b)) => TotalOrd::total_cmp(a, b),
+            (List(a), List(b)) => a.total_cmp(b),
+            (a, b) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_cmp_nan() {
+        assert_eq!(
+            TotalOrd::total_cmp(&f64::NAN, &f64::INFINITY),
+            Ordering::Greater
+        );
+        assert_eq!(TotalOrd::total_cmp(&f64::NAN, &-f64::NAN), Ordering::Equal);
+        assert_eq!(TotalOrd::total_cmp(&-0.0f32, &0.0), Ordering::Equal);
+        assert_eq!(
+            ScalarRefImpl::Float64(1.0).total_cmp(&ScalarRefImpl::Float64(f64::NAN)),
+            Ordering::Less
+        );
+    }
+}
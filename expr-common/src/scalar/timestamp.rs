@@ -0,0 +1,68 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+use std::fmt::{Debug, Display};
+
+use super::Date32;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_DAY: i64 = 86_400 * MICROS_PER_SECOND;
+
+/// A timestamp without time zone, stored as the number of microseconds since
+/// `1970-01-01 00:00:00`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    /// Get the date part of the timestamp.
+    pub fn date(self) -> Date32 {
+        Date32(self.0.div_euclid(MICROS_PER_DAY) as i32)
+    }
+
+    /// Get the number of microseconds since the start of the day.
+    pub fn micros_of_day(self) -> i64 {
+        self.0.rem_euclid(MICROS_PER_DAY)
+    }
+}
+
+/// Prints the timestamp in ISO 8601, e.g., `2022-03-09T12:34:56.000789`. The fraction part is
+/// omitted if it's zero.
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let micros = self.micros_of_day();
+        let seconds = micros / MICROS_PER_SECOND;
+        write!(
+            f,
+            "{}T{:02}:{:02}:{:02}",
+            self.date(),
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )?;
+        match micros % MICROS_PER_SECOND {
+            0 => Ok(()),
+            fraction => write!(f, ".{:06}", fraction),
+        }
+    }
+}
+
+impl Debug for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_display() {
+        assert_eq!(Timestamp(0).to_string(), "1970-01-01T00:00:00");
+        assert_eq!(
+            format!("{:?}", Timestamp(1_646_829_296_000_789)),
+            "2022-03-09T12:34:56.000789"
+        );
+        assert_eq!(Timestamp(-1).to_string(), "1969-12-31T23:59:59.999999");
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Aggregate functions, which fold all rows of the input into a single value.
+
+use std::cmp::Ordering;
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{Array, ArrayImpl};
+use expr_common::scalar::{ScalarImpl, TotalOrd};
+use expr_common::TypeMismatch;
+
+use crate::impl_::arith::CheckedArith;
+
+/// An aggregate function that consumes the input batch by batch.
+pub trait Aggregator {
+    /// Fold all rows of `input` into the state. Returns an error if the input type is not
+    /// supported, or is different from the previous batches.
+    fn update_batch(&mut self, input: &ArrayImpl) -> Result<()>;
+
+    /// Get the result of all batches so far.
+    fn output(&self) -> ScalarImpl;
+}
+
+/// Sum up non-null values in `iter`. Returns `None` if all values are null.
+fn checked_sum<T: CheckedArith>(iter: impl Iterator<Item = Option<T>>) -> Result<Option<T>> {
+    let mut sum = None;
+    for value in iter.flatten() {
+        sum = Some(match sum {
+            Some(sum) => T::checked_add(sum, value).ok_or_else(|| anyhow!("overflow in sum"))?,
+            None => value,
+        });
+    }
+    Ok(sum)
+}
+
+/// `sum(x)` over non-null values. Integers are summed as `i64` and floats as `f64`, so that summing
+/// an [`I32Array`] doesn't overflow easily. The output is null if all inputs are null.
+///
+/// [`I32Array`]: expr_common::array::I32Array
+#[derive(Default)]
+pub struct SumAgg {
+    sum: Option<ScalarImpl>,
+}
+
+impl SumAgg {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Aggregator for SumAgg {
+    fn update_batch(&mut self, input: &ArrayImpl) -> Result<()> {
+        let batch = match input {
+            ArrayImpl::Int16(a) => {
+                checked_sum(a.iter().map(|v| v.map(i64::from)))?.map(ScalarImpl::Int64)
+            }
+            ArrayImpl::Int32(a) => {
+                checked_sum(a.iter().map(|v| v.map(i64::from)))?.map(ScalarImpl::Int64)
+            }
+            ArrayImpl::Int64(a) => checked_sum(a.iter())?.map(ScalarImpl::Int64),
+            ArrayImpl::Float32(a) => {
+                checked_sum(a.iter().map(|v| v.map(f64::from)))?.map(ScalarImpl::Float64)
+            }
+            ArrayImpl::Float64(a) => checked_sum(a.iter())?.map(ScalarImpl::Float64),
+            ArrayImpl::Decimal(a) => checked_sum(a.iter())?.map(ScalarImpl::Decimal),
+            other => return Err(anyhow!("unsupported sum: {}", other.physical_type())),
+        };
+        let overflow = || anyhow!("overflow in sum");
+        let sum = match (&self.sum, batch) {
+            (Some(ScalarImpl::Int64(a)), Some(ScalarImpl::Int64(b))) => {
+                ScalarImpl::Int64(a.checked_add(b).ok_or_else(overflow)?)
+            }
+            (Some(ScalarImpl::Float64(a)), Some(ScalarImpl::Float64(b))) => {
+                ScalarImpl::Float64(a + b)
+            }
+            (Some(ScalarImpl::Decimal(a)), Some(ScalarImpl::Decimal(b))) => {
+                ScalarImpl::Decimal(a.checked_add(b).ok_or_else(overflow)?)
+            }
+            (Some(a), Some(b)) => {
+                return Err(TypeMismatch(a.physical_type(), b.physical_type()).into())
+            }
+            (_, None) => return Ok(()),
+            (None, Some(b)) => b,
+        };
+        self.sum = Some(sum);
+        Ok(())
+    }
+
+    fn output(&self) -> ScalarImpl {
+        self.sum.clone().unwrap_or(ScalarImpl::Null(()))
+    }
+}
+
+/// `count(x)`, which counts non-null values of any type. The output is never null.
+#[derive(Default)]
+pub struct CountAgg {
+    count: i64,
+}
+
+impl CountAgg {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Aggregator for CountAgg {
+    fn update_batch(&mut self, input: &ArrayImpl) -> Result<()> {
+        self.count += input.count_where(|_| true) as i64;
+        Ok(())
+    }
+
+    fn output(&self) -> ScalarImpl {
+        ScalarImpl::Int64(self.count)
+    }
+}
+
+/// Replace `state` with `value` if `value` is ordered as `ord` compared with `state`. Values are
+/// compared with [`TotalOrd`], so `NaN` is greater than all other floats.
+fn merge_extreme(
+    state: &mut Option<ScalarImpl>,
+    value: Option<ScalarImpl>,
+    ord: Ordering,
+) -> Result<()> {
+    match (state.as_ref(), value) {
+        (Some(s), Some(v)) if s.physical_type() != v.physical_type() => {
+            return Err(TypeMismatch(s.physical_type(), v.physical_type()).into())
+        }
+        (Some(s), Some(v)) => {
+            if v.as_scalar_ref().total_cmp(&s.as_scalar_ref()) == ord {
+                *state = Some(v);
+            }
+        }
+        (None, v) => *state = v,
+        (Some(_), None) => {}
+    }
+    Ok(())
+}
+
+/// `min(x)` over non-null values of any comparable type. The output is null if all inputs are
+/// null.
+#[derive(Default)]
+pub struct MinAgg {
+    min: Option<ScalarImpl>,
+}
+
+impl MinAgg {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Aggregator for MinAgg {
+    fn update_batch(&mut self, input: &ArrayImpl) -> Result<()> {
+        merge_extreme(&mut self.min, input.min_max().0, Ordering::Less)
+    }
+
+    fn output(&self) -> ScalarImpl {
+        self.min.clone().unwrap_or(ScalarImpl::Null(()))
+    }
+}
+
+/// `max(x)` over non-null values of any comparable type. The output is null if all inputs are
+/// null.
+#[derive(Default)]
+pub struct MaxAgg {
+    max: Option<ScalarImpl>,
+}
+
+impl MaxAgg {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Aggregator for MaxAgg {
+    fn update_batch(&mut self, input: &ArrayImpl) -> Result<()> {
+        merge_extreme(&mut self.max, input.min_max().1, Ordering::Greater)
+    }
+
+    fn output(&self) -> ScalarImpl {
+        self.max.clone().unwrap_or(ScalarImpl::Null(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expr_common::array::{F32Array, F64Array, I32Array, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn test_sum() {
+        let mut agg = SumAgg::new();
+        assert_eq!(agg.output(), ScalarImpl::Null(()));
+        agg.update_batch(&I32Array::from_slice(&[None]).into())
+            .unwrap();
+        assert_eq!(agg.output(), ScalarImpl::Null(()));
+        agg.update_batch(&I32Array::from_slice(&[Some(i32::MAX), None, Some(i32::MAX)]).into())
+            .unwrap();
+        agg.update_batch(&I32Array::from_slice(&[Some(2)]).into())
+            .unwrap();
+        assert_eq!(agg.output(), ScalarImpl::Int64(i32::MAX as i64 * 2 + 2));
+
+        let mut agg = SumAgg::new();
+        agg.update_batch(&F32Array::from_slice(&[Some(0.5), None, Some(1.0)]).into())
+            .unwrap();
+        assert_eq!(agg.output(), ScalarImpl::Float64(1.5));
+        assert!(agg
+            .update_batch(&I32Array::from_slice(&[Some(1)]).into())
+            .is_err());
+
+        assert!(SumAgg::new()
+            .update_batch(&StringArray::from_slice(&[Some("a")]).into())
+            .is_err());
+    }
+
+    #[test]
+    fn test_count() {
+        let mut agg = CountAgg::new();
+        assert_eq!(agg.output(), ScalarImpl::Int64(0));
+        agg.update_batch(&StringArray::from_slice(&[Some("a"), None, Some("")]).into())
+            .unwrap();
+        agg.update_batch(&StringArray::from_slice(&[None]).into())
+            .unwrap();
+        assert_eq!(agg.output(), ScalarImpl::Int64(2));
+    }
+
+    #[test]
+    fn test_min_max() {
+        let mut min = MinAgg::new();
+        let mut max = MaxAgg::new();
+        assert_eq!(min.output(), ScalarImpl::Null(()));
+        assert_eq!(max.output(), ScalarImpl::Null(()));
+
+        for batch in [
+            I32Array::from_slice(&[None, None]),
+            I32Array::from_slice(&[Some(3), None, Some(-1)]),
+            I32Array::from_slice(&[]),
+            I32Array::from_slice(&[Some(5), Some(0)]),
+        ] {
+            let batch = batch.into();
+            min.update_batch(&batch).unwrap();
+            max.update_batch(&batch).unwrap();
+        }
+        assert_eq!(min.output(), ScalarImpl::Int32(-1));
+        assert_eq!(max.output(), ScalarImpl::Int32(5));
+
+        let mut max = MaxAgg::new();
+        max.update_batch(&StringArray::from_slice(&[Some("a"), None, Some("b")]).into())
+            .unwrap();
+        assert_eq!(max.output(), ScalarImpl::String("b".to_string()));
+        assert!(max
+            .update_batch(&I32Array::from_slice(&[Some(1)]).into())
+            .is_err());
+    }
+
+    #[test]
+    fn test_min_max_nan() {
+        // `NaN` is the greatest float, no matter which batch it comes from.
+        for batches in [
+            [vec![Some(f64::NAN)], vec![Some(1.0), None, Some(2.0)]],
+            [vec![Some(1.0), None, Some(2.0)], vec![Some(f64::NAN)]],
+        ] {
+            let mut min = MinAgg::new();
+            let mut max = MaxAgg::new();
+            for batch in batches {
+                let batch = F64Array::from_slice(&batch).into();
+                min.update_batch(&batch).unwrap();
+                max.update_batch(&batch).unwrap();
+            }
+            assert_eq!(min.output(), ScalarImpl::Float64(1.0));
+            assert_eq!(max.output(), ScalarImpl::Float64(f64::NAN));
+        }
+    }
+}
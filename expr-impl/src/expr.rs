@@ -0,0 +1,37 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Expressions that cannot be expressed by vectorizing a scalar function.
+//!
+//! Expressions built from [`expr_template`] propagate nulls automatically and require the concrete
+//! array types at compile time. Expressions in this module work on [`ArrayImpl`] directly, so that
+//! they can handle nulls in a custom way or accept inputs of any type.
+//!
+//! [`ArrayImpl`]: expr_common::array::ArrayImpl
+
+mod case;
+mod coalesce;
+mod context;
+mod hash;
+mod is_null;
+mod list;
+mod literal;
+mod primitive;
+mod series;
+#[cfg(feature = "simd")]
+mod simd_cmp;
+mod type_of;
+mod width_bucket;
+
+pub use case::*;
+pub use coalesce::*;
+pub use context::*;
+pub use hash::*;
+pub use is_null::*;
+pub use list::*;
+pub use literal::*;
+pub use primitive::*;
+pub use series::*;
+#[cfg(feature = "simd")]
+pub use simd_cmp::*;
+pub use type_of::*;
+pub use width_bucket::*;
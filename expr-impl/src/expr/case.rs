@@ -0,0 +1,109 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements `CASE WHEN cond THEN a ELSE b END`
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{Array, ArrayImpl, BoolArray};
+use expr_common::expr::Expression;
+use expr_common::TypeMismatch;
+
+/// Picks the "then" value of each row where the condition is true, and the "else" value where it
+/// is false. The output is null where the condition is null.
+///
+/// Both value inputs must be of the same physical type.
+#[derive(Default)]
+pub struct CaseExpression;
+
+impl CaseExpression {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Evaluate the expression with the given arrays.
+    pub fn eval_batch(
+        &self,
+        cond: &ArrayImpl,
+        then: &ArrayImpl,
+        otherwise: &ArrayImpl,
+    ) -> Result<ArrayImpl> {
+        let cond: &BoolArray = cond.try_into()?;
+        if then.physical_type() != otherwise.physical_type() {
+            return Err(TypeMismatch(then.physical_type(), otherwise.physical_type()).into());
+        }
+        if cond.len() != then.len() || cond.len() != otherwise.len() {
+            return Err(anyhow!(
+                "array length mismatch: {}, {} and {}",
+                cond.len(),
+                then.len(),
+                otherwise.len()
+            ));
+        }
+        let mut builder = then.new_builder(cond.len());
+        for (idx, cond) in cond.iter().enumerate() {
+            match cond {
+                Some(true) => builder.try_push(then.get(idx))?,
+                Some(false) => builder.try_push(otherwise.get(idx))?,
+                None => builder.try_push(None)?,
+            }
+        }
+        Ok(builder.finish())
+    }
+}
+
+impl Expression for CaseExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        if data.len() != 3 {
+            return Err(anyhow!("Expect 3 inputs for CaseExpression"));
+        }
+        self.eval_batch(data[0], data[1], data[2])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expr_common::array::{I32Array, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn test_case() {
+        let result = CaseExpression::new()
+            .eval_expr(&[
+                &BoolArray::from_slice(&[Some(true), Some(false), None, Some(true), Some(false)])
+                    .into(),
+                &I32Array::from_slice(&[Some(1), Some(2), Some(3), None, Some(5)]).into(),
+                &I32Array::from_slice(&[Some(10), Some(20), Some(30), Some(40), None]).into(),
+            ])
+            .unwrap();
+        let result: I32Array = result.try_into().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(1), Some(20), None, None, None]
+        );
+    }
+
+    #[test]
+    fn test_case_mismatch() {
+        let cond: ArrayImpl = BoolArray::from_slice(&[Some(true)]).into();
+        let result = CaseExpression::new().eval_expr(&[
+            &cond,
+            &I32Array::from_slice(&[Some(1)]).into(),
+            &StringArray::from_slice(&[Some("1")]).into(),
+        ]);
+        assert!(result.unwrap_err().downcast::<TypeMismatch>().is_ok());
+
+        let result = CaseExpression::new().eval_expr(&[
+            &cond,
+            &I32Array::from_slice(&[Some(1), Some(2)]).into(),
+            &I32Array::from_slice(&[Some(1), Some(2)]).into(),
+        ]);
+        assert!(result.is_err());
+
+        let result = CaseExpression::new().eval_expr(&[
+            &I32Array::from_slice(&[Some(1)]).into(),
+            &I32Array::from_slice(&[Some(1)]).into(),
+            &I32Array::from_slice(&[Some(1)]).into(),
+        ]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements `COALESCE`
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{ArrayBuilderImpl, ArrayImpl, PhysicalType, StringArrayBuilder};
+use expr_common::expr::Expression;
+use expr_common::scalar::ScalarRefImpl;
+use expr_common::TypeMismatch;
+
+/// Returns the first non-null value among all inputs of each row, or null if all of them are
+/// null.
+///
+/// All inputs must be of the same physical type, or of numeric types. In the latter case, inputs
+/// are widened to their common numeric type, e.g., `COALESCE(i16, i32)` produces `i32`.
+#[derive(Default)]
+pub struct CoalesceExpression;
+
+impl CoalesceExpression {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Evaluate the expression with the given arrays.
+    pub fn eval_batch(&self, inputs: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        let (first, rest) = inputs
+            .split_first()
+            .ok_or_else(|| anyhow!("Expect at least 1 input for CoalesceExpression"))?;
+        let mut output_type = first.physical_type();
+        for input in rest {
+            if input.physical_type() != output_type {
+                output_type = output_type
+                    .common_numeric_type(input.physical_type())
+                    .ok_or_else(|| TypeMismatch(output_type, input.physical_type()))?;
+            }
+            if input.len() != first.len() {
+                return Err(anyhow!(
+                    "array length mismatch: {} and {}",
+                    first.len(),
+                    input.len()
+                ));
+            }
+        }
+        let columns = inputs
+            .iter()
+            .map(|input| move |idx| input.get(idx))
+            .collect::<Vec<_>>();
+        let mut builder = output_builder(output_type, inputs);
+        coalesce_by(first.len(), &columns, &mut builder)?;
+        Ok(builder.finish())
+    }
+}
+
+/// Create a builder that holds the output of `inputs` without reallocating.
+///
+/// The builder is sized to the row count. For strings, the data buffer is also sized to the
+/// largest data buffer among `inputs`, which is exact if most of the output comes from one input.
+fn output_builder(output_type: PhysicalType, inputs: &[&ArrayImpl]) -> ArrayBuilderImpl {
+    let len = inputs[0].len();
+    match output_type {
+        PhysicalType::String => {
+            let data_capacity = inputs
+                .iter()
+                .map(|input| match *input {
+                    ArrayImpl::String(array) => array.buffer_len(),
+                    _ => 0,
+                })
+                .max()
+                .unwrap_or(0);
+            StringArrayBuilder::with_data_capacity(len, data_capacity).into()
+        }
+        _ => ArrayBuilderImpl::with_capacity(output_type, len),
+    }
+}
+
+/// Push the first non-null value of each of the `len` rows among `columns` into `builder`.
+///
+/// `columns[i](idx)` reads row `idx` of the `i`-th input. As a column is only read at rows still
+/// null after all preceding columns, if the first column is mostly non-null, the remaining
+/// columns are barely touched. No intermediate array is built.
+///
+/// A value of a narrower numeric type than `builder` is widened when it is pushed, so that inputs
+/// of different numeric types are not cast as a whole.
+fn coalesce_by<'a, F>(len: usize, columns: &[F], builder: &mut ArrayBuilderImpl) -> Result<()>
+where
+    F: Fn(usize) -> Option<ScalarRefImpl<'a>>,
+{
+    let output_type = builder.physical_type();
+    for idx in 0..len {
+        match columns.iter().find_map(|column| column(idx)) {
+            Some(value) if value.physical_type() != output_type => {
+                builder.try_push_owned(Some(value.widen_to(output_type)?))?
+            }
+            value => builder.try_push(value)?,
+        }
+    }
+    Ok(())
+}
+
+impl Expression for CoalesceExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        self.eval_batch(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use expr_common::array::{Array, I16Array, I32Array, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn test_coalesce() {
+        let result = CoalesceExpression::new()
+            .eval_expr(&[
+                &I32Array::from_slice(&[Some(1), None, None, None]).into(),
+                &I32Array::from_slice(&[None, Some(2), None, None]).into(),
+                &I32Array::from_slice(&[Some(0), Some(0), Some(3), None]).into(),
+            ])
+            .unwrap();
+        let result: I32Array = result.try_into().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(1), Some(2), Some(3), None]
+        );
+    }
+
+    #[test]
+    fn test_coalesce_type_mismatch() {
+        let result = CoalesceExpression::new().eval_expr(&[
+            &I32Array::from_slice(&[Some(1)]).into(),
+            &StringArray::from_slice(&[Some("1")]).into(),
+        ]);
+        assert!(result.is_err());
+        assert!(CoalesceExpression::new().eval_expr(&[]).is_err());
+    }
+
+    #[test]
+    fn test_coalesce_widening() {
+        let result = CoalesceExpression::new()
+            .eval_expr(&[
+                &I16Array::from_slice(&[Some(1), None, None]).into(),
+                &I32Array::from_slice(&[Some(100_000), Some(100_000), None]).into(),
+            ])
+            .unwrap();
+        let result: I32Array = result.try_into().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(1), Some(100_000), None]
+        );
+    }
+
+    #[test]
+    fn test_coalesce_short_circuit() {
+        let inputs: [ArrayImpl; 3] = [
+            I32Array::from_slice(&[Some(1), None, None, Some(4)]).into(),
+            I32Array::from_slice(&[Some(10), Some(20), None, Some(40)]).into(),
+            I32Array::from_slice(&[Some(100), Some(200), Some(300), Some(400)]).into(),
+        ];
+        let reads = [Cell::new(0), Cell::new(0), Cell::new(0)];
+        let columns = inputs
+            .iter()
+            .zip(reads.iter())
+            .map(|(input, reads)| {
+                move |idx| {
+                    reads.set(reads.get() + 1);
+                    input.get(idx)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut builder = output_builder(PhysicalType::Int32, &[&inputs[0]]);
+        coalesce_by(4, &columns, &mut builder).unwrap();
+        let result: I32Array = builder.finish().try_into().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(1), Some(20), Some(300), Some(4)]
+        );
+        assert_eq!(
+            reads.iter().map(Cell::get).collect::<Vec<_>>(),
+            vec![4, 2, 1]
+        );
+    }
+
+    #[test]
+    fn test_coalesce_presized() {
+        let values = (0..10_000)
+            .map(|i| (i % 3 != 0).then(|| "value"))
+            .collect::<Vec<_>>();
+        let first: ArrayImpl = StringArray::from_slice(&values).into();
+        let second: ArrayImpl = StringArray::from_slice(&vec![Some("value"); 10_000]).into();
+        let inputs = [first, second];
+        let columns = inputs
+            .iter()
+            .map(|input| move |idx| input.get(idx))
+            .collect::<Vec<_>>();
+
+        let mut builder = output_builder(PhysicalType::String, &[&inputs[0], &inputs[1]]);
+        let data_capacity = match &builder {
+            ArrayBuilderImpl::String(builder) => builder.data_capacity(),
+            _ => unreachable!(),
+        };
+        assert!(data_capacity >= 50_000);
+        coalesce_by(10_000, &columns, &mut builder).unwrap();
+        match &builder {
+            ArrayBuilderImpl::String(builder) => assert_eq!(builder.data_capacity(), data_capacity),
+            _ => unreachable!(),
+        }
+        assert_eq!(builder.finish().len(), 10_000);
+    }
+}
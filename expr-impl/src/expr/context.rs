@@ -0,0 +1,189 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements [`EvalContext`] for evaluating expression trees
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{ArrayImpl, ArrayImplRef};
+use expr_common::expr::Expression;
+use expr_common::scalar::ScalarImpl;
+
+/// Identifies an array owned by an [`EvalContext`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArrayId(usize);
+
+/// Owns intermediate results of evaluating an expression tree.
+///
+/// Each array is evaluated once and stored in the context. Later nodes refer to it by
+/// [`ArrayId`] and borrow it, so a shared sub-expression (e.g., `a` in `(a + b) * (a - b)`) is
+/// neither re-evaluated nor cloned.
+///
+/// The context also holds values of bound parameters of a prepared statement, so that the same
+/// expression tree can run with different parameters.
+#[derive(Default)]
+pub struct EvalContext {
+    arrays: Vec<ArrayImpl>,
+    params: Vec<ScalarImpl>,
+}
+
+impl EvalContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a context with values of bound parameters.
+    pub fn with_params(params: Vec<ScalarImpl>) -> Self {
+        Self {
+            arrays: vec![],
+            params,
+        }
+    }
+
+    /// Get the value of the parameter at `index`.
+    pub fn param(&self, index: usize) -> Option<&ScalarImpl> {
+        self.params.get(index)
+    }
+
+    /// Move an array into the context, e.g., a column read from storage.
+    pub fn add(&mut self, array: ArrayImpl) -> ArrayId {
+        self.arrays.push(array);
+        ArrayId(self.arrays.len() - 1)
+    }
+
+    /// Borrow an array in the context.
+    pub fn get(&self, id: ArrayId) -> &ArrayImpl {
+        &self.arrays[id.0]
+    }
+
+    /// Borrow an array in the context as [`ArrayImplRef`].
+    pub fn get_ref(&self, id: ArrayId) -> ArrayImplRef<'_> {
+        self.get(id).as_ref()
+    }
+
+    /// Number of arrays owned by the context.
+    pub fn len(&self) -> usize {
+        self.arrays.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.arrays.is_empty()
+    }
+
+    /// Evaluate `expr` over arrays in the context, and store the result in the context.
+    pub fn eval(&mut self, expr: &dyn Expression, inputs: &[ArrayId]) -> Result<ArrayId> {
+        if let Some(id) = inputs.iter().find(|id| id.0 >= self.arrays.len()) {
+            return Err(anyhow!("array {:?} not found in context", id));
+        }
+        let inputs = inputs.iter().map(|&id| self.get(id)).collect::<Vec<_>>();
+        let result = expr.eval_expr(&inputs)?;
+        Ok(self.add(result))
+    }
+
+    /// Take the ownership of an array out of the context, e.g., the final result.
+    pub fn into_array(mut self, id: ArrayId) -> ArrayImpl {
+        self.arrays.swap_remove(id.0)
+    }
+}
+
+/// A parameter of a prepared statement, e.g., `$1`, which is resolved from [`EvalContext`] at
+/// execution.
+pub struct ParameterExpression {
+    /// The 0-based index of the parameter, i.e., `$1` has index 0.
+    index: usize,
+}
+
+impl ParameterExpression {
+    pub fn new(index: usize) -> Self {
+        Self { index }
+    }
+
+    /// Broadcast the bound value to `len` rows, and store the result in the context.
+    pub fn eval(&self, ctx: &mut EvalContext, len: usize) -> Result<ArrayId> {
+        let value = ctx
+            .param(self.index)
+            .ok_or_else(|| anyhow!("parameter ${} is not bound", self.index + 1))?;
+        let array = ArrayImpl::broadcast(value.as_scalar_ref(), len);
+        Ok(ctx.add(array))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use expr_common::array::{Array, BoolArray, I32Array};
+    use expr_template::BinaryExpression;
+
+    use super::*;
+
+    /// Counts the number of evaluations of the inner expression.
+    struct CountingExpression<E: Expression> {
+        inner: E,
+        count: Cell<usize>,
+    }
+
+    impl<E: Expression> CountingExpression<E> {
+        fn new(inner: E) -> Self {
+            Self {
+                inner,
+                count: Cell::new(0),
+            }
+        }
+    }
+
+    impl<E: Expression> Expression for CountingExpression<E> {
+        fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+            self.count.set(self.count.get() + 1);
+            self.inner.eval_expr(data)
+        }
+    }
+
+    #[test]
+    fn test_eval_context() {
+        let add = CountingExpression::new(BinaryExpression::<i32, i32, i32, _>::new(
+            |a: i32, b: i32| a + b,
+        ));
+        let sub = CountingExpression::new(BinaryExpression::<i32, i32, i32, _>::new(
+            |a: i32, b: i32| a - b,
+        ));
+        let mul = BinaryExpression::<i32, i32, i32, _>::new(|a: i32, b: i32| a * b);
+
+        let mut ctx = EvalContext::new();
+        let a = ctx.add(I32Array::from_slice(&[Some(3), Some(5), None]).into());
+        let b = ctx.add(I32Array::from_slice(&[Some(1), Some(2), Some(3)]).into());
+        let a_add_b = ctx.eval(&add, &[a, b]).unwrap();
+        let a_sub_b = ctx.eval(&sub, &[a, b]).unwrap();
+        let result = ctx.eval(&mul, &[a_add_b, a_sub_b]).unwrap();
+
+        assert_eq!(add.count.get(), 1);
+        assert_eq!(sub.count.get(), 1);
+        assert_eq!(ctx.len(), 5);
+        assert!(matches!(ctx.get_ref(a), ArrayImplRef::Int32(_)));
+
+        let result: I32Array = ctx.into_array(result).try_into().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(8), Some(21), None]
+        );
+        assert!(EvalContext::new()
+            .eval(&mul, &[ArrayId(0), ArrayId(1)])
+            .is_err());
+    }
+
+    #[test]
+    fn test_parameter() {
+        let gt = BinaryExpression::<i32, i32, bool, _>::new(|a: i32, b: i32| a > b);
+        let param = ParameterExpression::new(0);
+        let run = |value: i32| {
+            let mut ctx = EvalContext::with_params(vec![ScalarImpl::Int32(value)]);
+            let col = ctx.add(I32Array::from_slice(&[Some(3), Some(7), Some(12), None]).into());
+            let p = param.eval(&mut ctx, 4).unwrap();
+            let result = ctx.eval(&gt, &[col, p]).unwrap();
+            let result: BoolArray = ctx.into_array(result).try_into().unwrap();
+            result.iter().collect::<Vec<_>>()
+        };
+        assert_eq!(run(5), vec![Some(false), Some(true), Some(true), None]);
+        assert_eq!(run(10), vec![Some(false), Some(false), Some(true), None]);
+
+        assert!(param.eval(&mut EvalContext::new(), 4).is_err());
+    }
+}
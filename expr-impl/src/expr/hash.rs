@@ -0,0 +1,204 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements hash expressions for partitioning
+
+use std::hash::Hasher;
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{ArrayBuilder, ArrayImpl, I64ArrayBuilder};
+use expr_common::expr::Expression;
+
+/// `hash(x)`, which produces a 64-bit hash of each row, e.g., for `partition by hash(key) % n`.
+///
+/// Each value is written to a [`StableHasher`] with [`ScalarRefImpl::hash_stable`], which writes a
+/// fixed type tag and an explicit byte encoding of the value, so partition assignments don't change
+/// across runs, Rust releases or targets. Null input produces null.
+#[derive(Default)]
+pub struct HashExpression;
+
+/// A 64-bit FNV-1a hasher seeded with the standard offset basis.
+///
+/// Different from `DefaultHasher`, whose algorithm is unspecified and may change between Rust
+/// releases, the output only depends on the bytes written. Integers are written in little-endian,
+/// and `usize`s are written as `u64`s, so that the output doesn't depend on the target either.
+pub struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    pub fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16)
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32)
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128)
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as i64 as u64)
+    }
+}
+
+impl HashExpression {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Evaluate the expression with the given array.
+    pub fn eval_batch(&self, i1: &ArrayImpl) -> Result<ArrayImpl> {
+        let mut builder = I64ArrayBuilder::with_capacity(i1.len());
+        for idx in 0..i1.len() {
+            builder.push(i1.get(idx).map(|value| {
+                let mut hasher = StableHasher::new();
+                value.hash_stable(&mut hasher);
+                hasher.finish() as i64
+            }));
+        }
+        Ok(builder.finish().into())
+    }
+}
+
+impl Expression for HashExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        if data.len() != 1 {
+            return Err(anyhow!("Expect 1 input for HashExpression"));
+        }
+        self.eval_batch(data[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expr_common::array::{Array, DecimalArray, F64Array, I32Array, I64Array, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn test_hash() {
+        let input: ArrayImpl =
+            StringArray::from_slice(&[Some("a"), Some("b"), Some("a"), None]).into();
+        let result: I64Array = HashExpression::new()
+            .eval_expr(&[&input])
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(result.get(0), result.get(2));
+        assert_ne!(result.get(0), result.get(1));
+        assert_eq!(result.get(3), None);
+
+        let again: I64Array = HashExpression::new()
+            .eval_batch(&input)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            again.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_hash_golden() {
+        fn hash(input: ArrayImpl) -> Vec<Option<i64>> {
+            let result: I64Array = HashExpression::new()
+                .eval_batch(&input)
+                .unwrap()
+                .try_into()
+                .unwrap();
+            result.iter().collect()
+        }
+
+        // FNV-1a of the tag `2` followed by `1i32` in little-endian.
+        assert_eq!(
+            hash(I32Array::from_slice(&[Some(1)]).into()),
+            vec![Some(6782256274371576196)]
+        );
+        // FNV-1a of the tag `7`, the length `1u64` in little-endian and `"a"`.
+        assert_eq!(
+            hash(StringArray::from_slice(&[Some("a")]).into()),
+            vec![Some(-5526452071070321806)]
+        );
+
+        let result = hash(
+            F64Array::from_slice(&[Some(f64::NAN), Some(-f64::NAN), Some(0.0), Some(-0.0)]).into(),
+        );
+        assert_eq!(result[0], result[1]);
+        assert_eq!(result[2], result[3]);
+
+        let result = hash(
+            DecimalArray::from_slice(&[
+                Some("1.0".parse().unwrap()),
+                Some("1.00".parse().unwrap()),
+            ])
+            .into(),
+        );
+        assert_eq!(result[0], result[1]);
+    }
+
+    #[test]
+    fn test_stable_hasher() {
+        // Test vectors of 64-bit FNV-1a.
+        let mut hasher = StableHasher::new();
+        assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+
+        // Integers are written in little-endian regardless of the target.
+        let mut a = StableHasher::new();
+        a.write_i32(1);
+        let mut b = StableHasher::new();
+        b.write(&[1, 0, 0, 0]);
+        assert_eq!(a.finish(), b.finish());
+    }
+}
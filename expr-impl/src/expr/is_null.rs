@@ -0,0 +1,100 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements null test expressions
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{ArrayBuilder, ArrayImpl, BoolArrayBuilder};
+use expr_common::expr::Expression;
+
+/// Build a [`BoolArray`] of whether each element of `array` is null, or not null if `negated`.
+///
+/// [`BoolArray`]: expr_common::array::BoolArray
+fn eval_is_null(array: &ArrayImpl, negated: bool) -> ArrayImpl {
+    let mut builder = BoolArrayBuilder::with_capacity(array.len());
+    for idx in 0..array.len() {
+        builder.push(Some(array.get(idx).is_none() != negated));
+    }
+    builder.finish().into()
+}
+
+/// `x IS NULL`, which accepts input of any type. The output is never null.
+#[derive(Default)]
+pub struct IsNullExpression;
+
+impl IsNullExpression {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Evaluate the expression with the given array.
+    pub fn eval_batch(&self, i1: &ArrayImpl) -> Result<ArrayImpl> {
+        Ok(eval_is_null(i1, false))
+    }
+}
+
+impl Expression for IsNullExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        if data.len() != 1 {
+            return Err(anyhow!("Expect 1 input for IsNullExpression"));
+        }
+        self.eval_batch(data[0])
+    }
+}
+
+/// `x IS NOT NULL`, which accepts input of any type. The output is never null.
+#[derive(Default)]
+pub struct IsNotNullExpression;
+
+impl IsNotNullExpression {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Evaluate the expression with the given array.
+    pub fn eval_batch(&self, i1: &ArrayImpl) -> Result<ArrayImpl> {
+        Ok(eval_is_null(i1, true))
+    }
+}
+
+impl Expression for IsNotNullExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        if data.len() != 1 {
+            return Err(anyhow!("Expect 1 input for IsNotNullExpression"));
+        }
+        self.eval_batch(data[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expr_common::array::{Array, BoolArray, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn test_is_null() {
+        let input: ArrayImpl = StringArray::from_slice(&[Some("a"), None, Some(""), None]).into();
+
+        let result: BoolArray = IsNullExpression::new()
+            .eval_expr(&[&input])
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(false), Some(true), Some(false), Some(true)]
+        );
+
+        let result: BoolArray = IsNotNullExpression::new()
+            .eval_expr(&[&input])
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(true), Some(false), Some(true), Some(false)]
+        );
+
+        assert!(IsNullExpression::new().eval_expr(&[]).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements expressions over [`ListArray`]
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{
+    Array, ArrayBuilder, ArrayImpl, BoolArrayBuilder, ListArray, ListArrayBuilder,
+};
+use expr_common::expr::Expression;
+use expr_common::scalar::{ListRef, Scalar, ScalarRefImpl};
+
+/// Returns if `list` contains `item`, following the semantics of SQL `IN`: if `item` is not found
+/// and `list` contains a null, the result is unknown (null).
+fn list_contains(item: ScalarRefImpl<'_>, list: ListRef<'_>) -> Option<bool> {
+    let mut has_null = false;
+    for idx in 0..list.len() {
+        match list.get(idx) {
+            Some(x) if x == item => return Some(true),
+            Some(_) => {}
+            None => has_null = true,
+        }
+    }
+    if has_null {
+        None
+    } else {
+        Some(false)
+    }
+}
+
+/// `x IN list`, where `list` is given per row by a [`ListArray`].
+///
+/// The first input could be an array of any type, and the second input must be a [`ListArray`]
+/// of the same length. Row `i` of the output is whether the `i`-th list contains the `i`-th item.
+/// A null item or a null list produces null.
+#[derive(Default)]
+pub struct ArrayContainsExpression;
+
+impl ArrayContainsExpression {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Evaluate the expression with the given array.
+    pub fn eval_batch(&self, i1: &ArrayImpl, i2: &ArrayImpl) -> Result<ArrayImpl> {
+        let i2: &ListArray = i2.try_into()?;
+        if i1.len() != i2.len() {
+            return Err(anyhow!(
+                "array length mismatch: {} and {}",
+                i1.len(),
+                i2.len()
+            ));
+        }
+        let mut builder = BoolArrayBuilder::with_capacity(i1.len());
+        for (idx, list) in i2.iter().enumerate() {
+            match (i1.get(idx), list) {
+                (Some(item), Some(list)) => builder.push(list_contains(item, list)),
+                _ => builder.push(None),
+            }
+        }
+        Ok(builder.finish().into())
+    }
+}
+
+impl Expression for ArrayContainsExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        if data.len() != 2 {
+            return Err(anyhow!("Expect 2 inputs for ArrayContainsExpression"));
+        }
+        self.eval_batch(data[0], data[1])
+    }
+}
+
+/// `array_cat(a, b)`, which concatenates two lists of the same element type per row.
+///
+/// A null list is treated as empty, and the result is null only if both lists are null, which is
+/// the same as PostgreSQL.
+#[derive(Default)]
+pub struct ArrayCatExpression;
+
+impl ArrayCatExpression {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Evaluate the expression with the given array.
+    pub fn eval_batch(&self, i1: &ArrayImpl, i2: &ArrayImpl) -> Result<ArrayImpl> {
+        let i1: &ListArray = i1.try_into()?;
+        let i2: &ListArray = i2.try_into()?;
+        if i1.len() != i2.len() {
+            return Err(anyhow!(
+                "array length mismatch: {} and {}",
+                i1.len(),
+                i2.len()
+            ));
+        }
+        let mut builder = ListArrayBuilder::with_capacity(i1.len());
+        for (a, b) in i1.iter().zip(i2.iter()) {
+            match (a, b) {
+                (Some(a), Some(b)) => builder.push(Some(a.concat(&b)?.as_scalar_ref())),
+                (Some(x), None) | (None, Some(x)) => builder.push(Some(x)),
+                (None, None) => builder.push(None),
+            }
+        }
+        Ok(builder.finish().into())
+    }
+}
+
+impl Expression for ArrayCatExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        if data.len() != 2 {
+            return Err(anyhow!("Expect 2 inputs for ArrayCatExpression"));
+        }
+        self.eval_batch(data[0], data[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expr_common::array::I32Array;
+
+    use super::*;
+
+    fn build_list_array(lists: &[Option<&[Option<i32>]>]) -> ListArray {
+        let mut builder = ListArrayBuilder::with_capacity(lists.len());
+        for list in lists {
+            match list {
+                Some(list) => {
+                    let array: ArrayImpl = I32Array::from_slice(list).into();
+                    let array = array.into_boxed_array();
+                    builder.push(Some((&array).into()));
+                }
+                None => builder.push(None),
+            }
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn test_array_contains() {
+        let items: ArrayImpl =
+            I32Array::from_slice(&[Some(1), Some(3), None, Some(3), Some(4)]).into();
+        let lists: ArrayImpl = build_list_array(&[
+            Some(&[Some(1), Some(2)]),
+            Some(&[Some(1), Some(2)]),
+            Some(&[Some(1)]),
+            Some(&[Some(1), None]),
+            None,
+        ])
+        .into();
+        let result = ArrayContainsExpression::new()
+            .eval_expr(&[&items, &lists])
+            .unwrap();
+        assert_eq!(result.get(0), Some(ScalarRefImpl::Bool(true)));
+        assert_eq!(result.get(1), Some(ScalarRefImpl::Bool(false)));
+        assert_eq!(result.get(2), None);
+        assert_eq!(result.get(3), None);
+        assert_eq!(result.get(4), None);
+    }
+
+    #[test]
+    fn test_array_cat() {
+        let a: ArrayImpl =
+            build_list_array(&[Some(&[Some(1)]), Some(&[Some(2), Some(3)]), None, None]).into();
+        let b: ArrayImpl =
+            build_list_array(&[Some(&[Some(4), Some(5)]), Some(&[]), Some(&[None]), None]).into();
+        let result = ArrayCatExpression::new().eval_expr(&[&a, &b]).unwrap();
+        let result: ListArray = result.try_into().unwrap();
+        let expected = build_list_array(&[
+            Some(&[Some(1), Some(4), Some(5)]),
+            Some(&[Some(2), Some(3)]),
+            Some(&[None]),
+            None,
+        ]);
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            expected.iter().collect::<Vec<_>>()
+        );
+    }
+}
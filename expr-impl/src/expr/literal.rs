@@ -0,0 +1,88 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements typed `NULL` literals, e.g., `NULL::INTEGER`
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{ArrayBuilderImpl, ArrayImpl};
+use expr_common::datatype::DataType;
+use expr_common::expr::Expression;
+
+use super::{ArrayId, EvalContext};
+
+/// A `NULL` literal of a known type, which produces an all-null array of that type.
+///
+/// A literal has no input to learn the number of rows from. The batch length is either passed
+/// explicitly to [`NullLiteralExpression::eval`], or taken from the sibling columns passed to
+/// [`Expression::eval_expr`].
+pub struct NullLiteralExpression {
+    data_type: DataType,
+}
+
+impl NullLiteralExpression {
+    pub fn new(data_type: DataType) -> Self {
+        Self { data_type }
+    }
+
+    /// Get the type of the literal.
+    pub fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    /// Build an all-null array of `len` rows.
+    pub fn eval_batch(&self, len: usize) -> ArrayImpl {
+        let mut builder = ArrayBuilderImpl::with_capacity(self.data_type.physical_type(), len);
+        builder.append_n(len, None);
+        builder.finish()
+    }
+
+    /// Build an all-null array of `len` rows, and store the result in the context.
+    pub fn eval(&self, ctx: &mut EvalContext, len: usize) -> Result<ArrayId> {
+        Ok(ctx.add(self.eval_batch(len)))
+    }
+}
+
+/// The values of `data` are ignored. Only the length of the first array is used as the batch
+/// length.
+impl Expression for NullLiteralExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        let first = data
+            .first()
+            .ok_or_else(|| anyhow!("Expect at least 1 sibling column for NullLiteralExpression"))?;
+        Ok(self.eval_batch(first.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expr_common::array::{Array, I32Array};
+
+    use super::*;
+    use crate::expr::CoalesceExpression;
+
+    #[test]
+    fn test_null_literal_in_coalesce() {
+        // `COALESCE(NULL::INTEGER, col)`
+        let literal = NullLiteralExpression::new(DataType::Integer);
+        let mut ctx = EvalContext::new();
+        let col = ctx.add(I32Array::from_slice(&[Some(1), None, Some(3)]).into());
+        let null = literal.eval(&mut ctx, 3).unwrap();
+        let result = ctx.eval(&CoalesceExpression::new(), &[null, col]).unwrap();
+        let result: I32Array = ctx.into_array(result).try_into().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(1), None, Some(3)]
+        );
+    }
+
+    #[test]
+    fn test_null_literal_from_siblings() {
+        let literal = NullLiteralExpression::new(DataType::Varchar);
+        let sibling: ArrayImpl = I32Array::from_slice(&[Some(1), Some(2)]).into();
+        let result = literal.eval_expr(&[&sibling]).unwrap();
+        assert_eq!(result.len(), 2);
+        assert!(result.get(0).is_none() && result.get(1).is_none());
+        assert!(matches!(result, ArrayImpl::String(_)));
+
+        assert!(literal.eval_expr(&[]).is_err());
+    }
+}
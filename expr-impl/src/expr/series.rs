@@ -0,0 +1,84 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements set-returning functions
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{ArrayBuilder, I64Array, I64ArrayBuilder};
+
+/// The maximum number of values [`generate_series`] produces, so that a huge range returns an
+/// error instead of exhausting memory.
+pub const MAX_SERIES_LEN: usize = 1 << 26;
+
+/// `generate_series(start, stop, step)`, producing `[start, start + step, ...]` up to and
+/// including `stop`. A negative `step` counts down from `start` to `stop`.
+///
+/// This is a table function rather than a per-row expression, so it directly returns an array.
+/// Returns an error if the series would have more than [`MAX_SERIES_LEN`] values.
+pub fn generate_series(start: i64, stop: i64, step: i64) -> Result<I64Array> {
+    if step == 0 {
+        return Err(anyhow!("step size of generate_series cannot be zero"));
+    }
+    let len = if (step > 0 && start <= stop) || (step < 0 && start >= stop) {
+        let steps = (stop as i128 - start as i128) / step as i128;
+        usize::try_from(steps)
+            .ok()
+            .and_then(|steps| steps.checked_add(1))
+            .filter(|&len| len <= MAX_SERIES_LEN)
+            .ok_or_else(|| {
+                anyhow!(
+                    "generate_series({}, {}, {}) produces more than {} values",
+                    start,
+                    stop,
+                    step,
+                    MAX_SERIES_LEN
+                )
+            })?
+    } else {
+        0
+    };
+    let mut builder = I64ArrayBuilder::with_capacity(len);
+    let mut current = start;
+    for _ in 0..len {
+        builder.push(Some(current));
+        // The last step may overflow, but its value is never used.
+        current = current.wrapping_add(step);
+    }
+    Ok(builder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use expr_common::array::Array;
+
+    use super::*;
+
+    #[test]
+    fn test_generate_series() {
+        let array = generate_series(1, 10, 3).unwrap();
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some(1), Some(4), Some(7), Some(10)]
+        );
+
+        let array = generate_series(5, 0, -2).unwrap();
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some(5), Some(3), Some(1)]
+        );
+
+        assert!(generate_series(5, 0, 1).unwrap().is_empty());
+        assert_eq!(generate_series(i64::MAX - 1, i64::MAX, 5).unwrap().len(), 1);
+        assert!(generate_series(0, 10, 0).is_err());
+
+        assert!(generate_series(0, i64::MAX, 1).is_err());
+        assert!(generate_series(i64::MAX, i64::MIN, -1).is_err());
+        assert!(generate_series(1, MAX_SERIES_LEN as i64 + 1, 1).is_err());
+        assert_eq!(
+            generate_series(i64::MIN, i64::MAX, i64::MAX)
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![Some(i64::MIN), Some(-1), Some(i64::MAX - 1)]
+        );
+    }
+}
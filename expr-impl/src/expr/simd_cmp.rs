@@ -0,0 +1,224 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements comparisons over primitive arrays with portable SIMD
+
+use std::simd::Simd;
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{
+    Array, ArrayBuilder, ArrayImpl, BoolArray, BoolArrayBuilder, PrimitiveArray,
+};
+use expr_common::expr::Expression;
+
+use crate::impl_::cmp::{cmp_eq, cmp_ge, cmp_le};
+
+/// Number of values compared at once.
+const LANES: usize = 8;
+
+/// A comparison that [`SimdCmpExpression`] supports, which has the same semantics as the scalar
+/// function of the same name, e.g., [`SimdCmpOp::Le`] follows `cmp_le`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimdCmpOp {
+    Le,
+    Ge,
+    Eq,
+}
+
+/// Generate SIMD comparisons over arrays of the same primitive type.
+///
+/// Each item in the list `{ a, b, c }` represents:
+/// * 1st position: the function name.
+/// * 2nd position: the element type.
+/// * 3rd position: a function that checks if a SIMD vector contains `NaN`.
+macro_rules! impl_simd_cmp {
+    ($({ $name:ident, $t:ty, $has_nan:expr }),*) => {
+        $(
+            #[doc = concat!("Compare two arrays of `", stringify!($t), "` element-wise.")]
+            ///
+            /// Chunks of `LANES` values are compared with SIMD if neither input has nulls in the
+            /// chunk. Chunks with nulls or `NaN`s, and the tail shorter than `LANES`, fall back to
+            /// the scalar functions, so the output is the same as the scalar path.
+            ///
+            /// Panics if `a` and `b` are not of the same length.
+            pub fn $name(op: SimdCmpOp, a: &PrimitiveArray<$t>, b: &PrimitiveArray<$t>) -> BoolArray {
+                assert_eq!(a.len(), b.len(), "array length mismatch");
+                let scalar = |x: $t, y: $t| match op {
+                    SimdCmpOp::Le => cmp_le::<$t, $t, $t>(x, y),
+                    SimdCmpOp::Ge => cmp_ge::<$t, $t, $t>(x, y),
+                    SimdCmpOp::Eq => cmp_eq::<$t, $t, $t>(x, y),
+                };
+                let scalar_at = |idx: usize| match (a.get(idx), b.get(idx)) {
+                    (Some(x), Some(y)) => Some(scalar(x, y)),
+                    _ => None,
+                };
+                let has_nan: fn(Simd<$t, LANES>) -> bool = $has_nan;
+
+                let (a_data, a_bitmap) = a.as_arrow_slice();
+                let (b_data, b_bitmap) = b.as_arrow_slice();
+                let mut builder = BoolArrayBuilder::with_capacity(a.len());
+                let simd_len = a.len() / LANES * LANES;
+                for start in (0..simd_len).step_by(LANES) {
+                    let range = start..start + LANES;
+                    let x = Simd::<$t, LANES>::from_slice(&a_data[range.clone()]);
+                    let y = Simd::<$t, LANES>::from_slice(&b_data[range.clone()]);
+                    if a_bitmap[range.clone()].all()
+                        && b_bitmap[range.clone()].all()
+                        && !has_nan(x)
+                        && !has_nan(y)
+                    {
+                        // `cmp_le` and `cmp_ge` are strict comparisons.
+                        let mask = match op {
+                            SimdCmpOp::Le => x.lanes_lt(y),
+                            SimdCmpOp::Ge => x.lanes_gt(y),
+                            SimdCmpOp::Eq => x.lanes_eq(y),
+                        };
+                        for lane in 0..LANES {
+                            builder.push(Some(mask.test(lane)));
+                        }
+                    } else {
+                        for idx in range {
+                            builder.push(scalar_at(idx));
+                        }
+                    }
+                }
+                for idx in simd_len..a.len() {
+                    builder.push(scalar_at(idx));
+                }
+                builder.finish()
+            }
+        )*
+    };
+}
+
+impl_simd_cmp! {
+    { simd_cmp_i32, i32, |_| false },
+    { simd_cmp_f64, f64, |x| x.is_nan().any() }
+}
+
+/// Comparison between two `INTEGER` or two `DOUBLE` arrays with SIMD. See [`simd_cmp_i32`] and
+/// [`simd_cmp_f64`].
+///
+/// Comparisons across types require casts, and are not supported by this expression.
+pub struct SimdCmpExpression {
+    op: SimdCmpOp,
+}
+
+impl SimdCmpExpression {
+    pub fn new(op: SimdCmpOp) -> Self {
+        Self { op }
+    }
+
+    /// Evaluate the expression with the given array.
+    pub fn eval_batch(&self, i1: &ArrayImpl, i2: &ArrayImpl) -> Result<ArrayImpl> {
+        if i1.len() != i2.len() {
+            return Err(anyhow!(
+                "array length mismatch: {} and {}",
+                i1.len(),
+                i2.len()
+            ));
+        }
+        match (i1, i2) {
+            (ArrayImpl::Int32(a), ArrayImpl::Int32(b)) => Ok(simd_cmp_i32(self.op, a, b).into()),
+            (ArrayImpl::Float64(a), ArrayImpl::Float64(b)) => {
+                Ok(simd_cmp_f64(self.op, a, b).into())
+            }
+            (a, b) => Err(anyhow!(
+                "unsupported simd comparison: {} {:?} {}",
+                a.physical_type(),
+                self.op,
+                b.physical_type()
+            )),
+        }
+    }
+}
+
+impl Expression for SimdCmpExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        if data.len() != 2 {
+            return Err(anyhow!("Expect 2 inputs for SimdCmpExpression"));
+        }
+        self.eval_batch(data[0], data[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expr_common::array::{F64Array, I32Array};
+    use expr_template::BinaryExpression;
+
+    use super::*;
+
+    fn check_same_as_scalar(i1: ArrayImpl, i2: ArrayImpl) {
+        for op in [SimdCmpOp::Le, SimdCmpOp::Ge, SimdCmpOp::Eq] {
+            let result = SimdCmpExpression::new(op).eval_expr(&[&i1, &i2]).unwrap();
+            let expected = match (&i1, op) {
+                (ArrayImpl::Int32(_), SimdCmpOp::Le) => {
+                    BinaryExpression::<i32, i32, bool, _>::new(cmp_le::<i32, i32, i32>)
+                        .eval_batch(&i1, &i2)
+                }
+                (ArrayImpl::Int32(_), SimdCmpOp::Ge) => {
+                    BinaryExpression::<i32, i32, bool, _>::new(cmp_ge::<i32, i32, i32>)
+                        .eval_batch(&i1, &i2)
+                }
+                (ArrayImpl::Int32(_), SimdCmpOp::Eq) => {
+                    BinaryExpression::<i32, i32, bool, _>::new(cmp_eq::<i32, i32, i32>)
+                        .eval_batch(&i1, &i2)
+                }
+                (_, SimdCmpOp::Le) => {
+                    BinaryExpression::<f64, f64, bool, _>::new(cmp_le::<f64, f64, f64>)
+                        .eval_batch(&i1, &i2)
+                }
+                (_, SimdCmpOp::Ge) => {
+                    BinaryExpression::<f64, f64, bool, _>::new(cmp_ge::<f64, f64, f64>)
+                        .eval_batch(&i1, &i2)
+                }
+                (_, SimdCmpOp::Eq) => {
+                    BinaryExpression::<f64, f64, bool, _>::new(cmp_eq::<f64, f64, f64>)
+                        .eval_batch(&i1, &i2)
+                }
+            }
+            .unwrap();
+            assert_eq!(result, expected, "{:?}", op);
+        }
+    }
+
+    #[test]
+    fn test_simd_cmp_i32() {
+        // 10007 values, so that there's a tail shorter than `LANES`, with sparse nulls.
+        let i1: I32Array = (0..10007)
+            .map(|i| (i % 97 != 0).then(|| (i * 7919) % 101))
+            .collect();
+        let i2: I32Array = (0..10007)
+            .map(|i| (i % 89 != 0).then(|| (i * 104729) % 103))
+            .collect();
+        check_same_as_scalar(i1.into(), i2.into());
+    }
+
+    #[test]
+    fn test_simd_cmp_f64() {
+        let i1: F64Array = (0..10007)
+            .map(|i| match i % 97 {
+                0 => None,
+                1 => Some(f64::NAN),
+                2 => Some(-0.0),
+                _ => Some(((i * 7919) % 101) as f64 / 4.0),
+            })
+            .collect();
+        let i2: F64Array = (0..10007)
+            .map(|i| match i % 89 {
+                0 => None,
+                1 => Some(f64::NAN),
+                2 => Some(0.0),
+                _ => Some(((i * 104729) % 103) as f64 / 4.0),
+            })
+            .collect();
+        check_same_as_scalar(i1.into(), i2.into());
+
+        assert!(SimdCmpExpression::new(SimdCmpOp::Le)
+            .eval_expr(&[
+                &I32Array::from_slice(&[Some(1)]).into(),
+                &F64Array::from_slice(&[Some(1.0)]).into()
+            ])
+            .is_err());
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements expressions over the type of the input
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{ArrayBuilder, ArrayImpl, StringArrayBuilder};
+use expr_common::datatype::DataType;
+use expr_common::expr::Expression;
+
+/// `typeof(x)`, which returns the name of the input's [`DataType`] for every row.
+///
+/// The result only depends on the type of the input, so it is never null, even if `x` is null.
+pub struct TypeOfExpression {
+    type_name: String,
+}
+
+impl TypeOfExpression {
+    pub fn new(input_type: &DataType) -> Self {
+        Self {
+            type_name: input_type.to_string(),
+        }
+    }
+
+    /// Evaluate the expression with the given array.
+    pub fn eval_batch(&self, i1: &ArrayImpl) -> Result<ArrayImpl> {
+        let mut builder = StringArrayBuilder::with_capacity(i1.len());
+        for _ in 0..i1.len() {
+            builder.push(Some(&self.type_name));
+        }
+        Ok(builder.finish().into())
+    }
+}
+
+impl Expression for TypeOfExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        if data.len() != 1 {
+            return Err(anyhow!("Expect 1 input for TypeOfExpression"));
+        }
+        self.eval_batch(data[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expr_common::array::{Array, I32Array, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn test_type_of() {
+        let input: ArrayImpl = I32Array::from_slice(&[Some(1), None, Some(3)]).into();
+        let result = TypeOfExpression::new(&DataType::Integer)
+            .eval_expr(&[&input])
+            .unwrap();
+        let result: StringArray = result.try_into().unwrap();
+        assert_eq!(result.iter().collect::<Vec<_>>(), vec![Some("INTEGER"); 3]);
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements `width_bucket`
+
+use anyhow::{anyhow, Result};
+use expr_common::array::{Array, ArrayImpl, F64Array, I32Array};
+use expr_common::expr::Expression;
+use expr_template::FnArgs4Expression;
+
+use crate::impl_::math::width_bucket;
+
+type WidthBucketFunc = fn(f64, f64, f64, i32) -> i32;
+
+/// `width_bucket(value, low, high, count)` over `f64` values and `i32` bucket count.
+///
+/// This is a [`FnArgs4Expression`] over [`width_bucket`], which additionally validates the operands
+/// of each row as PostgreSQL does: a `NaN` operand, an infinite bound, `low == high` or a
+/// non-positive `count` is an error. Nulls are propagated.
+pub struct WidthBucketExpression {
+    inner: FnArgs4Expression<f64, f64, f64, i32, i32, WidthBucketFunc>,
+}
+
+impl Default for WidthBucketExpression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WidthBucketExpression {
+    pub fn new() -> Self {
+        Self {
+            inner: FnArgs4Expression::<f64, f64, f64, i32, i32, WidthBucketFunc>::new(width_bucket),
+        }
+    }
+
+    /// Evaluate the expression with the given array.
+    pub fn eval_batch(
+        &self,
+        value: &ArrayImpl,
+        low: &ArrayImpl,
+        high: &ArrayImpl,
+        count: &ArrayImpl,
+    ) -> Result<ArrayImpl> {
+        let value_array: &F64Array = value.try_into()?;
+        let low_array: &F64Array = low.try_into()?;
+        let high_array: &F64Array = high.try_into()?;
+        let count_array: &I32Array = count.try_into()?;
+        if value_array.iter().flatten().any(f64::is_nan) {
+            return Err(anyhow!("operand cannot be NaN"));
+        }
+        for (low, high) in low_array.iter().zip(high_array.iter()) {
+            if let (Some(low), Some(high)) = (low, high) {
+                if low.is_nan() || high.is_nan() {
+                    return Err(anyhow!("lower bound and upper bound cannot be NaN"));
+                }
+                if low.is_infinite() || high.is_infinite() {
+                    return Err(anyhow!(
+                        "lower bound and upper bound must be finite: {}, {}",
+                        low,
+                        high
+                    ));
+                }
+                if low == high {
+                    return Err(anyhow!("lower bound cannot equal upper bound: {}", low));
+                }
+            }
+        }
+        for count in count_array.iter().flatten() {
+            if count <= 0 {
+                return Err(anyhow!("count must be greater than zero: {}", count));
+            }
+        }
+        self.inner.eval_batch(value, low, high, count)
+    }
+}
+
+impl Expression for WidthBucketExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl> {
+        if data.len() != 4 {
+            return Err(anyhow!("Expect 4 inputs for WidthBucketExpression"));
+        }
+        self.eval_batch(data[0], data[1], data[2], data[3])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(value: &[Option<f64>], low: f64, high: f64, count: i32) -> Result<Vec<Option<i32>>> {
+        let len = value.len();
+        let result = WidthBucketExpression::new().eval_expr(&[
+            &F64Array::from_slice(value).into(),
+            &F64Array::from_slice(&vec![Some(low); len]).into(),
+            &F64Array::from_slice(&vec![Some(high); len]).into(),
+            &I32Array::from_slice(&vec![Some(count); len]).into(),
+        ])?;
+        let result: I32Array = result.try_into()?;
+        Ok(result.iter().collect())
+    }
+
+    #[test]
+    fn test_width_bucket() {
+        assert_eq!(
+            eval(&[Some(5.0), Some(15.0), Some(25.0), None], 0.0, 20.0, 4).unwrap(),
+            vec![Some(2), Some(4), Some(5), None]
+        );
+        assert_eq!(
+            eval(&[Some(-1.0), Some(0.0), Some(20.0)], 0.0, 20.0, 4).unwrap(),
+            vec![Some(0), Some(1), Some(5)]
+        );
+        assert_eq!(
+            eval(&[Some(5.0), Some(15.0), Some(25.0)], 20.0, 0.0, 4).unwrap(),
+            vec![Some(4), Some(2), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_width_bucket_invalid() {
+        assert!(eval(&[Some(5.0)], 1.0, 1.0, 4).is_err());
+        assert!(eval(&[Some(5.0)], 0.0, 20.0, -1).is_err());
+        assert!(eval(&[Some(5.0)], 0.0, 20.0, 0).is_err());
+
+        assert!(eval(&[Some(1.0), Some(f64::NAN)], 0.0, 20.0, 4).is_err());
+        assert!(eval(&[Some(5.0)], f64::NAN, 20.0, 4).is_err());
+        assert!(eval(&[Some(5.0)], 0.0, f64::NAN, 4).is_err());
+        assert!(eval(&[Some(5.0)], f64::NEG_INFINITY, 20.0, 4).is_err());
+        assert!(eval(&[Some(5.0)], 0.0, f64::INFINITY, 4).is_err());
+
+        // An infinite operand is still valid.
+        assert_eq!(
+            eval(
+                &[Some(f64::INFINITY), Some(f64::NEG_INFINITY)],
+                0.0,
+                20.0,
+                4
+            )
+            .unwrap(),
+            vec![Some(5), Some(0)]
+        );
+    }
+}
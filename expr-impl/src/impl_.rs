@@ -1,6 +1,9 @@
 // Copyright 2022 Alex Chi. Licensed under Apache-2.0.
 
+pub mod arith;
 pub mod cmp;
+pub mod logic;
+pub mod math;
 pub mod string;
 
 #[cfg(test)]
@@ -0,0 +1,96 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements arithmetic functions for [`Array`] types
+
+use anyhow::{anyhow, Result};
+pub use expr_common::scalar::CheckedArith;
+use expr_common::scalar::Scalar;
+
+/// Return `i1 + i2`. Note that `i1` and `i2` could be different types. This function will
+/// automatically cast them into the output type `O`.
+///
+/// Returns an error on overflow.
+///
+/// * `I1`: left input type.
+/// * `I2`: right input type.
+/// * `O`: cast type and output type.
+pub fn add<I1: Scalar, I2: Scalar, O: Scalar>(i1: I1::RefType<'_>, i2: I2::RefType<'_>) -> Result<O>
+where
+    for<'a> I1::RefType<'a>: Into<O::RefType<'a>>,
+    for<'a> I2::RefType<'a>: Into<O::RefType<'a>>,
+    for<'a> O: CheckedArith + From<O::RefType<'a>>,
+{
+    let i1 = I1::upcast_gat(i1);
+    let i2 = I2::upcast_gat(i2);
+    O::from(i1.into())
+        .checked_add(O::from(i2.into()))
+        .ok_or_else(|| anyhow!("overflow in add"))
+}
+
+/// Return `i1 - i2`. Note that `i1` and `i2` could be different types. This function will
+/// automatically cast them into the output type `O`.
+///
+/// Returns an error on overflow.
+///
+/// * `I1`: left input type.
+/// * `I2`: right input type.
+/// * `O`: cast type and output type.
+pub fn sub<I1: Scalar, I2: Scalar, O: Scalar>(i1: I1::RefType<'_>, i2: I2::RefType<'_>) -> Result<O>
+where
+    for<'a> I1::RefType<'a>: Into<O::RefType<'a>>,
+    for<'a> I2::RefType<'a>: Into<O::RefType<'a>>,
+    for<'a> O: CheckedArith + From<O::RefType<'a>>,
+{
+    let i1 = I1::upcast_gat(i1);
+    let i2 = I2::upcast_gat(i2);
+    O::from(i1.into())
+        .checked_sub(O::from(i2.into()))
+        .ok_or_else(|| anyhow!("overflow in sub"))
+}
+
+/// Return `i1 * i2`. Note that `i1` and `i2` could be different types. This function will
+/// automatically cast them into the output type `O`.
+///
+/// Returns an error on overflow.
+///
+/// * `I1`: left input type.
+/// * `I2`: right input type.
+/// * `O`: cast type and output type.
+pub fn mul<I1: Scalar, I2: Scalar, O: Scalar>(i1: I1::RefType<'_>, i2: I2::RefType<'_>) -> Result<O>
+where
+    for<'a> I1::RefType<'a>: Into<O::RefType<'a>>,
+    for<'a> I2::RefType<'a>: Into<O::RefType<'a>>,
+    for<'a> O: CheckedArith + From<O::RefType<'a>>,
+{
+    let i1 = I1::upcast_gat(i1);
+    let i2 = I2::upcast_gat(i2);
+    O::from(i1.into())
+        .checked_mul(O::from(i2.into()))
+        .ok_or_else(|| anyhow!("overflow in mul"))
+}
+
+/// Return `i1 / i2`. Note that `i1` and `i2` could be different types. This function will
+/// automatically cast them into the output type `O`.
+///
+/// Returns an error if `i2` is zero. Same as PostgreSQL, this also applies to floats, which would
+/// otherwise produce an infinity. Returns an error on overflow, e.g., `i32::MIN / -1`.
+///
+/// * `I1`: left input type.
+/// * `I2`: right input type.
+/// * `O`: cast type and output type.
+pub fn div<I1: Scalar, I2: Scalar, O: Scalar>(i1: I1::RefType<'_>, i2: I2::RefType<'_>) -> Result<O>
+where
+    for<'a> I1::RefType<'a>: Into<O::RefType<'a>>,
+    for<'a> I2::RefType<'a>: Into<O::RefType<'a>>,
+    for<'a> O: CheckedArith + From<O::RefType<'a>> + PartialEq + Default,
+{
+    let i1 = I1::upcast_gat(i1);
+    let i2 = I2::upcast_gat(i2);
+    let i2 = O::from(i2.into());
+    if i2 == O::default() {
+        return Err(anyhow!("division by zero"));
+    }
+    O::from(i1.into())
+        .checked_div(i2)
+        .ok_or_else(|| anyhow!("overflow in div"))
+}
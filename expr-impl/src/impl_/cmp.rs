@@ -5,9 +5,11 @@
 use std::cmp::Ordering;
 
 use expr_common::scalar::Scalar;
+pub use expr_common::scalar::TotalOrd;
 
 /// Return if `i1 < i2`. Note that `i1` and `i2` could be different types. This
-/// function will automatically cast them into `C` type.
+/// function will automatically cast them into `C` type. `NaN` is greater than all other floats,
+/// see [`TotalOrd`].
 ///
 /// * `I1`: left input type.
 /// * `I2`: right input type.
@@ -16,15 +18,16 @@ pub fn cmp_le<I1: Scalar, I2: Scalar, C: Scalar>(i1: I1::RefType<'_>, i2: I2::Re
 where
     for<'a> I1::RefType<'a>: Into<C::RefType<'a>>,
     for<'a> I2::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a> C::RefType<'a>: PartialOrd,
+    for<'a> C::RefType<'a>: TotalOrd,
 {
     let i1 = I1::upcast_gat(i1);
     let i2 = I2::upcast_gat(i2);
-    i1.into().partial_cmp(&i2.into()).unwrap() == Ordering::Less
+    TotalOrd::total_cmp(&i1.into(), &i2.into()) == Ordering::Less
 }
 
 /// Return if `i1 > i2`. Note that `i1` and `i2` could be different types. This
-/// function will automatically cast them into `C` type.
+/// function will automatically cast them into `C` type. `NaN` is greater than all other floats,
+/// see [`TotalOrd`].
 ///
 /// * `I1`: left input type.
 /// * `I2`: right input type.
@@ -33,11 +36,11 @@ pub fn cmp_ge<I1: Scalar, I2: Scalar, C: Scalar>(i1: I1::RefType<'_>, i2: I2::Re
 where
     for<'a> I1::RefType<'a>: Into<C::RefType<'a>>,
     for<'a> I2::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a> C::RefType<'a>: PartialOrd,
+    for<'a> C::RefType<'a>: TotalOrd,
 {
     let i1 = I1::upcast_gat(i1);
     let i2 = I2::upcast_gat(i2);
-    i1.into().partial_cmp(&i2.into()).unwrap() == Ordering::Greater
+    TotalOrd::total_cmp(&i1.into(), &i2.into()) == Ordering::Greater
 }
 
 /// Return if `i1 == i2`. Note that `i1` and `i2` could be different types. This
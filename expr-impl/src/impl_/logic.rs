@@ -0,0 +1,81 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements logical functions with SQL's three-valued logic, where `NULL` means "unknown".
+//!
+//! These functions take `Option`s, and should be wrapped in nullable expressions, so that
+//! `true OR NULL` could be `true` instead of `NULL`.
+
+/// `i1 AND i2`. The result is `false` if any input is `false`, even if the other one is `NULL`.
+pub fn and(i1: Option<bool>, i2: Option<bool>) -> Option<bool> {
+    match (i1, i2) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+/// `i1 OR i2`. The result is `true` if any input is `true`, even if the other one is `NULL`.
+pub fn or(i1: Option<bool>, i2: Option<bool>) -> Option<bool> {
+    match (i1, i2) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    }
+}
+
+/// `NOT i1`. `NOT NULL` is `NULL`.
+pub fn not(i1: Option<bool>) -> Option<bool> {
+    i1.map(|x| !x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T: Option<bool> = Some(true);
+    const F: Option<bool> = Some(false);
+    const N: Option<bool> = None;
+
+    #[test]
+    fn test_and() {
+        let table = [
+            (T, T, T),
+            (T, F, F),
+            (T, N, N),
+            (F, T, F),
+            (F, F, F),
+            (F, N, F),
+            (N, T, N),
+            (N, F, F),
+            (N, N, N),
+        ];
+        for (i1, i2, expected) in table {
+            assert_eq!(and(i1, i2), expected, "{:?} AND {:?}", i1, i2);
+        }
+    }
+
+    #[test]
+    fn test_or() {
+        let table = [
+            (T, T, T),
+            (T, F, T),
+            (T, N, T),
+            (F, T, T),
+            (F, F, F),
+            (F, N, N),
+            (N, T, T),
+            (N, F, N),
+            (N, N, N),
+        ];
+        for (i1, i2, expected) in table {
+            assert_eq!(or(i1, i2), expected, "{:?} OR {:?}", i1, i2);
+        }
+    }
+
+    #[test]
+    fn test_not() {
+        assert_eq!(not(T), F);
+        assert_eq!(not(F), T);
+        assert_eq!(not(N), N);
+    }
+}
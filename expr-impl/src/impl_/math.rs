@@ -0,0 +1,26 @@
+// Copyright 2022 Alex Chi. Licensed under Apache-2.0.
+
+//! Implements math functions for [`Array`] types
+
+/// Return the bucket `value` falls in, among `count` equal-width buckets spanning `low` to `high`.
+///
+/// Buckets are numbered from `1` to `count`. Values before the first bucket return `0`, and values
+/// after the last bucket return `count + 1`. `low` could be greater than `high`, in which case the
+/// buckets are numbered in the reverse order.
+///
+/// The caller must ensure that no operand is `NaN`, `low` and `high` are finite, `low != high` and
+/// `count > 0`.
+pub fn width_bucket(value: f64, low: f64, high: f64, count: i32) -> i32 {
+    let (value, low, high) = if low < high {
+        (value, low, high)
+    } else {
+        (-value, -low, -high)
+    };
+    if value < low {
+        0
+    } else if value >= high {
+        count + 1
+    } else {
+        ((value - low) / (high - low) * count as f64) as i32 + 1
+    }
+}
@@ -4,6 +4,134 @@
 
 #![allow(dead_code)]
 
+use expr_common::array::{ArrayBuilder, ArrayImpl, StringArrayBuilder};
+use expr_common::scalar::List;
+
 pub fn str_contains(i1: &str, i2: &str) -> bool {
     i1.contains(i2)
 }
+
+/// `a || b`, which concatenates two strings.
+pub fn str_concat(a: &str, b: &str) -> String {
+    let mut s = String::with_capacity(a.len() + b.len());
+    s.push_str(a);
+    s.push_str(b);
+    s
+}
+
+/// SQL `concat(a, b)`. Unlike [`str_concat`], `NULL` is treated as an empty string, so the output
+/// is never null. This should be wrapped in a nullable expression.
+pub fn sql_concat(a: Option<&str>, b: Option<&str>) -> Option<String> {
+    Some(str_concat(a.unwrap_or_default(), b.unwrap_or_default()))
+}
+
+/// Split `s` by `delim` into a list of strings, e.g., `str_split('a,b,c', ',')` is
+/// `['a', 'b', 'c']`. An empty `s` is split into a single empty string, and an empty `delim`
+/// doesn't split `s` at all.
+pub fn str_split(s: &str, delim: &str) -> List {
+    let mut builder = StringArrayBuilder::with_capacity(0);
+    if delim.is_empty() {
+        builder.push(Some(s));
+    } else {
+        for part in s.split(delim) {
+            builder.push(Some(part));
+        }
+    }
+    List::from(ArrayImpl::from(builder.finish()))
+}
+
+/// SQL `upper(s)`.
+pub fn str_upper(s: &str) -> String {
+    s.to_uppercase()
+}
+
+/// SQL `lower(s)`.
+pub fn str_lower(s: &str) -> String {
+    s.to_lowercase()
+}
+
+/// SQL `length(s)`, which counts characters rather than bytes, e.g., `length('é')` is 1.
+pub fn str_length(s: &str) -> i32 {
+    s.chars().count() as i32
+}
+
+/// SQL `trim(s)`, which removes leading and trailing whitespaces.
+pub fn str_trim(s: &str) -> String {
+    s.trim().to_string()
+}
+
+/// A single element of a `LIKE` pattern.
+#[derive(Clone, Copy, PartialEq)]
+enum LikeToken {
+    /// `%`, which matches any sequence of characters, including an empty one.
+    Any,
+    /// `_`, which matches exactly one character.
+    One,
+    /// Any other character, or a character escaped by `\`.
+    Literal(char),
+}
+
+/// SQL `s LIKE pattern`. `%` matches any substring, `_` matches a single character, and `\`
+/// escapes the next character so that it's matched literally. A trailing `\` matches itself.
+pub fn str_like(s: &str, pattern: &str) -> bool {
+    let mut tokens = vec![];
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '%' => LikeToken::Any,
+            '_' => LikeToken::One,
+            '\\' => LikeToken::Literal(chars.next().unwrap_or('\\')),
+            c => LikeToken::Literal(c),
+        });
+    }
+    let s = s.chars().collect::<Vec<_>>();
+
+    // Greedy matching. On a mismatch, backtrack to the last `%` and let it consume one more
+    // character.
+    let (mut si, mut ti) = (0, 0);
+    let mut backtrack = None;
+    while si < s.len() {
+        match tokens.get(ti) {
+            Some(LikeToken::Any) => {
+                backtrack = Some((si, ti));
+                ti += 1;
+                continue;
+            }
+            Some(LikeToken::One) => {
+                si += 1;
+                ti += 1;
+                continue;
+            }
+            Some(LikeToken::Literal(c)) if *c == s[si] => {
+                si += 1;
+                ti += 1;
+                continue;
+            }
+            _ => {}
+        }
+        match backtrack {
+            Some((bs, bt)) => {
+                backtrack = Some((bs + 1, bt));
+                si = bs + 1;
+                ti = bt + 1;
+            }
+            None => return false,
+        }
+    }
+    tokens[ti..].iter().all(|t| *t == LikeToken::Any)
+}
+
+/// SQL `substring(s, start, len)`, which takes `len` characters starting from the 1-based position
+/// `start`. Positions before the first character are counted but produce nothing, e.g.,
+/// `substring('abc', 0, 2)` is `'a'`. A negative `len` produces an empty string.
+pub fn substring(s: &str, start: i32, len: i32) -> String {
+    let end = start as i64 + len.max(0) as i64;
+    let start = start.max(1) as i64;
+    if end <= start {
+        return String::new();
+    }
+    s.chars()
+        .skip(start as usize - 1)
+        .take((end - start) as usize)
+        .collect()
+}
@@ -1,8 +1,10 @@
 // Copyright 2022 Alex Chi. Licensed under Apache-2.0.
 
 use expr_common::array::Array;
-use expr_common::scalar::Scalar;
-use expr_template::BinaryExpression;
+use expr_common::scalar::{List, Scalar};
+use expr_template::{
+    BinaryExpression, NullableBinaryExpression, TernaryExpression, UnaryExpression,
+};
 
 fn test_if_impl<A: Scalar, B: Scalar, O: Scalar, F: Fn(A::RefType<'_>, B::RefType<'_>) -> O>(_: F) {
 }
@@ -41,7 +43,8 @@ fn test_simple_generics_function() {
     test_if_impl::<i32, f32, i64, _>(binary_generics::<i32, f32, i64>)
 }
 
-use expr_common::array::{BoolArray, I32Array, StringArray};
+use expr_common::array::{ArrayImpl, BoolArray, F64Array, I32Array, StringArray};
+use expr_common::expr::Expression;
 
 use super::cmp::*;
 use super::string::*;
@@ -87,6 +90,78 @@ fn test_cmp_ge_str() {
     );
 }
 
+#[test]
+fn test_cmp_nan() {
+    // `NaN` is greater than all other values and equal to itself.
+    let nan = f64::NAN;
+    let i1 = F64Array::from_slice(&[Some(nan), Some(1.0), Some(nan), Some(f64::INFINITY), None]);
+    let i2 = F64Array::from_slice(&[Some(1.0), Some(nan), Some(nan), Some(nan), Some(nan)]);
+
+    let expr = BinaryExpression::<f64, f64, bool, _>::new(cmp_le::<f64, f64, f64>);
+    let result = expr
+        .eval_batch(&i1.clone().into(), &i2.clone().into())
+        .unwrap();
+    check_array_eq::<BoolArray>(
+        (&result).try_into().unwrap(),
+        &[Some(false), Some(true), Some(false), Some(true), None],
+    );
+
+    let expr = BinaryExpression::<f64, f64, bool, _>::new(cmp_ge::<f64, f64, f64>);
+    let result = expr.eval_batch(&i1.into(), &i2.into()).unwrap();
+    check_array_eq::<BoolArray>(
+        (&result).try_into().unwrap(),
+        &[Some(true), Some(false), Some(false), Some(false), None],
+    );
+
+    // `-0.0` still equals `0.0`.
+    assert!(!cmp_le::<f64, f64, f64>(-0.0, 0.0));
+    assert!(!cmp_le::<f32, f32, f64>(-f32::NAN, f32::INFINITY));
+}
+
+#[test]
+fn test_eval_batch_without_nulls() {
+    let expr = BinaryExpression::<String, String, bool, _>::new(str_contains);
+    let i1 = [Some("000"), Some("111"), Some("")];
+    let i2 = [Some("0"), Some("0"), Some("")];
+
+    // Without nulls, the fast path is taken, and the output has no nulls either.
+    let fast: BoolArray = expr
+        .eval_batch(
+            &StringArray::from_slice(&i1).into(),
+            &StringArray::from_slice(&i2).into(),
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert!(!fast.has_nulls());
+
+    // A null in any input forces the slow path, which should produce the same values for the
+    // other rows.
+    let slow: BoolArray = expr
+        .eval_batch(
+            &StringArray::from_slice(&[&i1[..], &[Some("0")]].concat()).into(),
+            &StringArray::from_slice(&[&i2[..], &[None]].concat()).into(),
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(
+        slow.iter().collect::<Vec<_>>(),
+        fast.iter().chain([None]).collect::<Vec<_>>()
+    );
+    check_array_eq(&fast, &[Some(true), Some(false), Some(true)]);
+}
+
+#[test]
+fn test_eval_batch_length_mismatch() {
+    let expr = BinaryExpression::<String, String, bool, _>::new(str_contains);
+    let i1: ArrayImpl = StringArray::from_slice(&[Some("a"), Some("b")]).into();
+    let i2: ArrayImpl = StringArray::from_slice(&[Some("a"), Some("b"), Some("c")]).into();
+    let err = expr.eval_batch(&i1, &i2).unwrap_err();
+    assert_eq!(err.to_string(), "array length mismatch: 2 and 3");
+    assert!(expr.eval_expr(&[&i2, &i1]).is_err());
+}
+
 #[test]
 fn test_str_contains() {
     let expr = BinaryExpression::<String, String, bool, _>::new(str_contains);
@@ -102,6 +177,125 @@ fn test_str_contains() {
     );
 }
 
+#[test]
+fn test_str_concat() {
+    let i1: ArrayImpl = StringArray::from_slice(&[Some("a"), None, Some("a"), None]).into();
+    let i2: ArrayImpl = StringArray::from_slice(&[Some("你好"), Some("b"), None, None]).into();
+
+    let result = BinaryExpression::<String, String, String, _>::new(str_concat)
+        .eval_batch(&i1, &i2)
+        .unwrap();
+    check_array_eq::<StringArray>(
+        (&result).try_into().unwrap(),
+        &[Some("a你好"), None, None, None],
+    );
+
+    let result = NullableBinaryExpression::<String, String, String, _>::new(sql_concat)
+        .eval_batch(&i1, &i2)
+        .unwrap();
+    check_array_eq::<StringArray>(
+        (&result).try_into().unwrap(),
+        &[Some("a你好"), Some("b"), Some("a"), Some("")],
+    );
+}
+
+#[test]
+fn test_str_split() {
+    use expr_common::array::ListArray;
+    use expr_common::scalar::ScalarRefImpl;
+
+    let expr = BinaryExpression::<String, String, List, _>::new(str_split);
+    let result = expr
+        .eval_batch(
+            &StringArray::from_slice(&[Some("a,b,c"), Some(""), None, Some("a,,b")]).into(),
+            &StringArray::from_slice(&[Some(","), Some(","), Some(","), Some("")]).into(),
+        )
+        .unwrap();
+    let result: ListArray = result.try_into().unwrap();
+    let items = |idx: usize| {
+        let list = result.get(idx).unwrap();
+        (0..list.len())
+            .map(|i| match list.get(i) {
+                Some(ScalarRefImpl::String(s)) => s.to_string(),
+                other => panic!("unexpected item: {:?}", other),
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(items(0), vec!["a", "b", "c"]);
+    assert_eq!(items(1), vec![""]);
+    assert!(result.get(2).is_none());
+    assert_eq!(items(3), vec!["a,,b"]);
+}
+
+#[test]
+fn test_str_unary_functions() {
+    let input: ArrayImpl = StringArray::from_slice(&[Some(" Héllo "), Some(""), None]).into();
+
+    let result = UnaryExpression::<String, String, _>::new(str_upper)
+        .eval_expr(&[&input])
+        .unwrap();
+    check_array_eq::<StringArray>(
+        (&result).try_into().unwrap(),
+        &[Some(" HÉLLO "), Some(""), None],
+    );
+
+    let result = UnaryExpression::<String, String, _>::new(str_lower)
+        .eval_expr(&[&input])
+        .unwrap();
+    check_array_eq::<StringArray>(
+        (&result).try_into().unwrap(),
+        &[Some(" héllo "), Some(""), None],
+    );
+
+    let result = UnaryExpression::<String, i32, _>::new(str_length)
+        .eval_expr(&[&input])
+        .unwrap();
+    check_array_eq::<I32Array>((&result).try_into().unwrap(), &[Some(7), Some(0), None]);
+    assert_eq!(str_length("é"), 1);
+    assert_eq!(str_length("你好"), 2);
+
+    let result = UnaryExpression::<String, String, _>::new(str_trim)
+        .eval_expr(&[&input])
+        .unwrap();
+    check_array_eq::<StringArray>(
+        (&result).try_into().unwrap(),
+        &[Some("Héllo"), Some(""), None],
+    );
+    assert_eq!(str_trim(" \t\n"), "");
+}
+
+#[test]
+fn test_str_like() {
+    let expr = BinaryExpression::<String, String, bool, _>::new(str_like);
+    let result = expr
+        .eval_batch(
+            &StringArray::from_slice(&[Some("hello"), Some("hello"), None]).into(),
+            &StringArray::from_slice(&[Some("h%o"), Some("h_o"), Some("%")]).into(),
+        )
+        .unwrap();
+    check_array_eq::<BoolArray>(
+        (&result).try_into().unwrap(),
+        &[Some(true), Some(false), None],
+    );
+
+    assert!(str_like("", ""));
+    assert!(!str_like("a", ""));
+    assert!(str_like("", "%"));
+    assert!(str_like("", "%%"));
+    assert!(str_like("abc", "a%%c"));
+    assert!(str_like("abc", "%%%"));
+    assert!(str_like("你好", "__"));
+    assert!(!str_like("abc", "__"));
+    assert!(str_like("abcbc", "%bc"));
+    assert!(str_like("aXbXc", "a%b%c"));
+    assert!(!str_like("aXbXd", "a%b%c"));
+    assert!(str_like("100%", "100\\%"));
+    assert!(!str_like("1000", "100\\%"));
+    assert!(str_like("a_b", "a\\_b"));
+    assert!(!str_like("axb", "a\\_b"));
+    assert!(str_like("a\\", "a\\"));
+}
+
 #[test]
 fn test_str_contains_lambda() {
     let expr =
@@ -117,3 +311,167 @@ fn test_str_contains_lambda() {
         &[Some(true), Some(false), None],
     );
 }
+
+#[test]
+fn test_unary_not() {
+    let expr = UnaryExpression::<bool, bool, _>::new(|x: bool| !x);
+    let result = expr
+        .eval_batch(&BoolArray::from_slice(&[Some(true), Some(false), None]).into())
+        .unwrap();
+    check_array_eq::<BoolArray>(
+        (&result).try_into().unwrap(),
+        &[Some(false), Some(true), None],
+    );
+}
+
+#[test]
+fn test_unary_neg() {
+    let expr = UnaryExpression::<i32, i32, _>::new(|x: i32| -x);
+    let input: ArrayImpl = I32Array::from_slice(&[Some(1), None, Some(-3)]).into();
+    let result = expr.eval_expr(&[&input]).unwrap();
+    check_array_eq::<I32Array>((&result).try_into().unwrap(), &[Some(-1), None, Some(3)]);
+
+    assert!(expr.eval_expr(&[&input, &input]).is_err());
+}
+
+#[test]
+fn test_substring() {
+    let expr = TernaryExpression::<String, i32, i32, String, _>::new(substring);
+    let result = expr
+        .eval_batch(
+            &StringArray::from_slice(&[
+                Some("hello"),
+                Some("hello"),
+                Some("你好世界"),
+                None,
+                Some("abc"),
+            ])
+            .into(),
+            &I32Array::from_slice(&[Some(2), Some(0), Some(2), Some(1), Some(1)]).into(),
+            &I32Array::from_slice(&[Some(3), Some(2), Some(10), Some(1), None]).into(),
+        )
+        .unwrap();
+    check_array_eq::<StringArray>(
+        (&result).try_into().unwrap(),
+        &[Some("ell"), Some("h"), Some("好世界"), None, None],
+    );
+    assert_eq!(substring("abc", 2, -1), "");
+}
+
+#[test]
+fn test_nullable_safe_div() {
+    // `a / NULLIF(b, 0)`: the output is null on zero divisor, even if both inputs are non-null.
+    let expr = NullableBinaryExpression::<i32, i32, i32, _>::new(
+        |a: Option<i32>, b: Option<i32>| match (a, b) {
+            (Some(a), Some(b)) if b != 0 => Some(a / b),
+            _ => None,
+        },
+    );
+    let result = expr
+        .eval_batch(
+            &I32Array::from_slice(&[Some(6), Some(6), None, Some(6)]).into(),
+            &I32Array::from_slice(&[Some(3), Some(0), Some(1), None]).into(),
+        )
+        .unwrap();
+    check_array_eq::<I32Array>((&result).try_into().unwrap(), &[Some(2), None, None, None]);
+
+    // Unlike `BinaryExpression`, the function may produce a value from null inputs.
+    let expr =
+        NullableBinaryExpression::<i32, i32, i32, _>::new(|a: Option<i32>, b: Option<i32>| {
+            Some(a.unwrap_or(0) + b.unwrap_or(0))
+        });
+    let result = expr
+        .eval_batch(
+            &I32Array::from_slice(&[Some(1), None]).into(),
+            &I32Array::from_slice(&[None, None]).into(),
+        )
+        .unwrap();
+    check_array_eq::<I32Array>((&result).try_into().unwrap(), &[Some(1), Some(0)]);
+}
+
+#[test]
+fn test_eval_constant_inputs() {
+    use expr_common::expr::{Input, Output};
+    use expr_common::scalar::{ScalarImpl, ScalarRefImpl};
+
+    let expr = BinaryExpression::<i32, i32, i32, _>::new(|a: i32, b: i32| a + b);
+
+    // `2 + 3` is evaluated once, and the output is not materialized.
+    let output = expr
+        .eval_inputs(
+            Input::Constant(Some(ScalarRefImpl::Int32(2)), 1000),
+            Input::Constant(Some(ScalarRefImpl::Int32(3)), 1000),
+        )
+        .unwrap();
+    match &output {
+        Output::Constant { value, len, .. } => {
+            assert_eq!(value, &Some(ScalarImpl::Int32(5)));
+            assert_eq!(*len, 1000);
+        }
+        Output::Array(_) => panic!("expect a constant output"),
+    }
+    assert_eq!(output.into_array().len(), 1000);
+
+    let output = expr
+        .eval_inputs(
+            Input::Constant(None, 2),
+            Input::Constant(Some(ScalarRefImpl::Int32(3)), 2),
+        )
+        .unwrap();
+    check_array_eq::<I32Array>((&output.into_array()).try_into().unwrap(), &[None, None]);
+
+    // Constants are broadcast if any input is an array.
+    let array: ArrayImpl = I32Array::from_slice(&[Some(1), None]).into();
+    let output = expr
+        .eval_inputs(
+            Input::Array(&array),
+            Input::Constant(Some(ScalarRefImpl::Int32(3)), 2),
+        )
+        .unwrap();
+    assert!(matches!(output, Output::Array(_)));
+    check_array_eq::<I32Array>((&output.into_array()).try_into().unwrap(), &[Some(4), None]);
+
+    assert!(expr
+        .eval_inputs(
+            Input::Constant(Some(ScalarRefImpl::String("2")), 1),
+            Input::Constant(Some(ScalarRefImpl::Int32(3)), 1),
+        )
+        .is_err());
+}
+
+#[test]
+fn test_eval_batch_scalar_right() {
+    use expr_common::scalar::ScalarRefImpl;
+
+    let array: ArrayImpl = I32Array::from_slice(&[Some(1), None, Some(3)]).into();
+    let expr = BinaryExpression::<i32, i32, i32, _>::new(|a: i32, b: i32| a + b);
+    let result = expr
+        .eval_batch_scalar_right(&array, ScalarRefImpl::Int32(1))
+        .unwrap();
+
+    // Compare with materializing the constant as an array.
+    let constant: ArrayImpl = I32Array::from_slice(&[Some(1); 3]).into();
+    let expected = expr.eval_batch(&array, &constant).unwrap();
+    check_array_eq::<I32Array>((&result).try_into().unwrap(), &[Some(2), None, Some(4)]);
+    check_array_eq::<I32Array>((&expected).try_into().unwrap(), &[Some(2), None, Some(4)]);
+
+    let array: ArrayImpl = StringArray::from_slice(&[Some("a"), None, Some("bc")]).into();
+    let expr = BinaryExpression::<String, String, bool, _>::new(|a: &str, b: &str| a < b);
+    let result = expr
+        .eval_batch_scalar_right(&array, ScalarRefImpl::String("b"))
+        .unwrap();
+    let constant: ArrayImpl = StringArray::from_slice(&[Some("b"); 3]).into();
+    let expected = expr.eval_batch(&array, &constant).unwrap();
+    check_array_eq::<BoolArray>(
+        (&result).try_into().unwrap(),
+        &[Some(true), None, Some(false)],
+    );
+    check_array_eq::<BoolArray>(
+        (&expected).try_into().unwrap(),
+        &[Some(true), None, Some(false)],
+    );
+
+    assert!(expr
+        .eval_batch_scalar_right(&array, ScalarRefImpl::Int32(1))
+        .is_err());
+}
@@ -2,12 +2,21 @@
 
 //! Expression framework based on array
 
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+pub mod agg;
+pub mod expr;
 mod impl_;
 
+use anyhow::{anyhow, Result};
 use expr_common::datatype::DataType;
 use expr_common::expr::Expression;
+use expr_common::scalar::List;
 use expr_macro_rules::datatype_macros::*;
-use expr_template::BinaryExpression;
+use expr_template::{
+    BinaryExpression, FallibleBinaryExpression, NullableBinaryExpression, NullableUnaryExpression,
+    UnaryExpression,
+};
 
 /// All supported expression functions
 pub enum ExpressionFunc {
@@ -16,6 +25,26 @@ pub enum ExpressionFunc {
     CmpEq,
     CmpNe,
     StrContains,
+    StrLike,
+    StrUpper,
+    StrLower,
+    StrLength,
+    StrTrim,
+    StrConcat,
+    Concat,
+    StrSplit,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    And,
+    Or,
+    Not,
+    Case,
+    TypeOf,
+    Hash,
+    IsNull,
+    IsNotNull,
 }
 
 /// Composes all combinations of possible comparisons
@@ -69,6 +98,84 @@ macro_rules! for_all_cmp_combinations {
     };
 }
 
+/// Composes all combinations of possible arithmetic operations
+///
+/// Each item in the list `{ a, b, c }` represents:
+/// * 1st position: left input type.
+/// * 2nd position: right input type.
+/// * 3rd position: cast type, which is also the output type. Casting follows the same rules as
+///   [`for_all_cmp_combinations`].
+macro_rules! for_all_arith_combinations {
+    ($macro:ident $(, $x:ident)*) => {
+        $macro! {
+            [$($x),*],
+            // arithmetic for the same type
+            { int16, int16, int16 },
+            { int32, int32, int32 },
+            { int64, int64, int64 },
+            { float32, float32, float32 },
+            { float64, float64, float64 },
+            { decimal, decimal, decimal },
+            // arithmetic across integer types
+            { int16, int32, int32 },
+            { int32, int16, int32 },
+            { int16, int64, int64 },
+            { int32, int64, int64 },
+            { int64, int16, int64 },
+            { int64, int32, int64 },
+            // arithmetic across float types
+            { float32, float64, float64 },
+            { float64, float32, float64 },
+            // arithmetic across integer and float32 types
+            { int16, float32, float32 },
+            { float32, int16, float32 },
+            { int32, float32, float64 },
+            { float32, int32, float64 },
+            // arithmetic across integer and float64 types
+            { int32, float64, float64 },
+            { float64, int32, float64 },
+            { int16, float64, float64 },
+            { float64, int16, float64 },
+            // arithmetic with decimal types
+            { int16, decimal, decimal },
+            { decimal, int16, decimal },
+            { int32, decimal, decimal },
+            { decimal, int32, decimal },
+            { int64, decimal, decimal },
+            { decimal, int64, decimal }
+        }
+    };
+}
+
+/// Generate all variants of arithmetic expressions. Arithmetic functions return an error on
+/// overflow, so they are wrapped in [`FallibleBinaryExpression`].
+macro_rules! impl_arith_expression_of {
+    ([$i1t:ident, $i2t:ident, $arith_func:ident], $({ $i1:ident, $i2:ident, $convert:ident }),*) => {
+        match ($i1t, $i2t) {
+            $(
+                ($i1! { datatype_match_pattern }, $i2! { datatype_match_pattern }) => {
+                    Box::new(FallibleBinaryExpression::<
+                        $i1! { datatype_scalar },
+                        $i2! { datatype_scalar },
+                        $convert! { datatype_scalar },
+                        _
+                    >::new(
+                        $arith_func::<
+                            $i1! { datatype_scalar },
+                            $i2! { datatype_scalar },
+                            $convert! { datatype_scalar }
+                        >,
+                    ))
+                }
+            )*
+            (other_dt1, other_dt2) => return Err(anyhow!("unsupported arithmetic: {:?} <{}> {:?}",
+                other_dt1,
+                stringify!($arith_func),
+                other_dt2))
+        }
+    };
+}
+
 /// Generate all variants of comparison expressions
 macro_rules! impl_cmp_expression_of {
     ([$i1t:ident, $i2t:ident, $cmp_func:ident], $({ $i1:ident, $i2:ident, $convert:ident }),*) => {
@@ -100,25 +207,40 @@ macro_rules! impl_cmp_expression_of {
                     ))
                 }
             )*
-            (other_dt1, other_dt2) => unimplemented!("unsupported comparison: {:?} <{}> {:?}",
+            (other_dt1, other_dt2) => return Err(anyhow!("unsupported comparison: {:?} <{}> {:?}",
                 other_dt1,
                 stringify!($cmp_func),
-                other_dt2)
+                other_dt2))
         }
     };
 }
 
-/// Build expression with runtime information.
+/// Build expression with runtime information. Returns an error if the function doesn't support
+/// the input types.
 pub fn build_binary_expression(
     f: ExpressionFunc,
     i1: DataType,
     i2: DataType,
-) -> Box<dyn Expression> {
+) -> Result<Box<dyn Expression>> {
+    use impl_::arith::*;
     use impl_::cmp::*;
+    use impl_::logic;
     use impl_::string::*;
     use ExpressionFunc::*;
 
-    match f {
+    #[cfg(feature = "simd")]
+    if let (CmpLe | CmpGe | CmpEq, DataType::Integer, DataType::Integer)
+    | (CmpLe | CmpGe | CmpEq, DataType::Double, DataType::Double) = (&f, &i1, &i2)
+    {
+        let op = match f {
+            CmpLe => expr::SimdCmpOp::Le,
+            CmpGe => expr::SimdCmpOp::Ge,
+            _ => expr::SimdCmpOp::Eq,
+        };
+        return Ok(Box::new(expr::SimdCmpExpression::new(op)));
+    }
+
+    let expr: Box<dyn Expression> = match f {
         CmpLe => for_all_cmp_combinations! { impl_cmp_expression_of, i1, i2, cmp_le },
         CmpGe => for_all_cmp_combinations! { impl_cmp_expression_of, i1, i2, cmp_ge },
         CmpEq => for_all_cmp_combinations! { impl_cmp_expression_of, i1, i2, cmp_eq },
@@ -126,12 +248,111 @@ pub fn build_binary_expression(
         StrContains => Box::new(BinaryExpression::<String, String, bool, _>::new(
             str_contains,
         )),
-    }
+        StrLike => Box::new(BinaryExpression::<String, String, bool, _>::new(str_like)),
+        StrConcat => Box::new(BinaryExpression::<String, String, String, _>::new(
+            str_concat,
+        )),
+        Concat => Box::new(NullableBinaryExpression::<String, String, String, _>::new(
+            sql_concat,
+        )),
+        StrSplit => Box::new(BinaryExpression::<String, String, List, _>::new(str_split)),
+        Add => for_all_arith_combinations! { impl_arith_expression_of, i1, i2, add },
+        Sub => for_all_arith_combinations! { impl_arith_expression_of, i1, i2, sub },
+        Mul => for_all_arith_combinations! { impl_arith_expression_of, i1, i2, mul },
+        Div => for_all_arith_combinations! { impl_arith_expression_of, i1, i2, div },
+        And | Or => {
+            if !matches!((&i1, &i2), (DataType::Boolean, DataType::Boolean)) {
+                return Err(anyhow!("unsupported logical operation: {:?}, {:?}", i1, i2));
+            }
+            let func = if matches!(f, And) {
+                logic::and
+            } else {
+                logic::or
+            };
+            Box::new(NullableBinaryExpression::<bool, bool, bool, _>::new(func))
+        }
+        Not | Case | TypeOf | Hash | IsNull | IsNotNull | StrUpper | StrLower | StrLength
+        | StrTrim => return Err(anyhow!("not a binary expression")),
+    };
+    Ok(expr)
+}
+
+/// Build unary expression with runtime information.
+pub fn build_unary_expression(f: ExpressionFunc, i1: DataType) -> Result<Box<dyn Expression>> {
+    use impl_::string::*;
+    use ExpressionFunc::*;
+
+    let expr: Box<dyn Expression> = match f {
+        TypeOf => Box::new(expr::TypeOfExpression::new(&i1)),
+        Hash => Box::new(expr::HashExpression::new()),
+        IsNull => Box::new(expr::IsNullExpression::new()),
+        IsNotNull => Box::new(expr::IsNotNullExpression::new()),
+        StrUpper | StrLower | StrTrim => {
+            if !matches!(i1, DataType::Char { .. } | DataType::Varchar) {
+                return Err(anyhow!("unsupported string function: {:?}", i1));
+            }
+            let func = match f {
+                StrUpper => str_upper,
+                StrLower => str_lower,
+                _ => str_trim,
+            };
+            Box::new(UnaryExpression::<String, String, _>::new(func))
+        }
+        StrLength => {
+            if !matches!(i1, DataType::Char { .. } | DataType::Varchar) {
+                return Err(anyhow!("unsupported string function: {:?}", i1));
+            }
+            Box::new(UnaryExpression::<String, i32, _>::new(str_length))
+        }
+        Not => {
+            if !matches!(i1, DataType::Boolean) {
+                return Err(anyhow!("unsupported logical operation: NOT {:?}", i1));
+            }
+            Box::new(NullableUnaryExpression::<bool, bool, _>::new(
+                impl_::logic::not,
+            ))
+        }
+        CmpLe | CmpGe | CmpEq | CmpNe | StrContains | StrLike | StrConcat | Concat | StrSplit
+        | Add | Sub | Mul | Div | And | Or | Case => return Err(anyhow!("not a unary expression")),
+    };
+    Ok(expr)
+}
+
+/// Build ternary expression with runtime information. Returns an error if the function doesn't
+/// support the input types.
+pub fn build_ternary_expression(
+    f: ExpressionFunc,
+    i1: DataType,
+    i2: DataType,
+    i3: DataType,
+) -> Result<Box<dyn Expression>> {
+    use ExpressionFunc::*;
+
+    let expr: Box<dyn Expression> = match f {
+        Case => match (i1, i2.physical_type(), i3.physical_type()) {
+            (DataType::Boolean, then, otherwise) if then == otherwise => {
+                Box::new(expr::CaseExpression::new())
+            }
+            (cond, then, otherwise) => {
+                return Err(anyhow!(
+                    "unsupported case: {:?} ? {} : {}",
+                    cond,
+                    then,
+                    otherwise
+                ))
+            }
+        },
+        _ => return Err(anyhow!("not a ternary expression")),
+    };
+    Ok(expr)
 }
 
 #[cfg(test)]
 mod tests {
-    use expr_common::array::{Array, F64Array, I16Array, StringArray};
+    use expr_common::array::{
+        Array, ArrayImpl, BoolArray, DecimalArray, F32Array, F64Array, I16Array, I32Array,
+        I64Array, StringArray,
+    };
     use expr_common::scalar::ScalarRefImpl;
 
     use super::*;
@@ -142,7 +363,8 @@ mod tests {
             ExpressionFunc::StrContains,
             DataType::Varchar,
             DataType::Char { width: 10 },
-        );
+        )
+        .unwrap();
 
         for _ in 0..10 {
             let result = expr
@@ -157,10 +379,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_build_str_like() {
+        let expr = build_binary_expression(
+            ExpressionFunc::StrLike,
+            DataType::Varchar,
+            DataType::Varchar,
+        )
+        .unwrap();
+        let result = expr
+            .eval_expr(&[
+                &StringArray::from_slice(&[Some("100%"), Some("1000"), None]).into(),
+                &StringArray::from_slice(&[Some("1%\\%"), Some("1%\\%"), Some("%")]).into(),
+            ])
+            .unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::Bool(true));
+        assert_eq!(result.get(1).unwrap(), ScalarRefImpl::Bool(false));
+        assert!(result.get(2).is_none());
+    }
+
+    #[test]
+    fn test_build_concat() {
+        let expr =
+            build_binary_expression(ExpressionFunc::Concat, DataType::Varchar, DataType::Varchar)
+                .unwrap();
+        let result = expr
+            .eval_expr(&[
+                &StringArray::from_slice(&[Some("a"), None, Some("a")]).into(),
+                &StringArray::from_slice(&[None, None, Some("b")]).into(),
+            ])
+            .unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::String("a"));
+        assert_eq!(result.get(1).unwrap(), ScalarRefImpl::String(""));
+        assert_eq!(result.get(2).unwrap(), ScalarRefImpl::String("ab"));
+    }
+
+    #[test]
+    fn test_build_str_length() {
+        let expr = build_unary_expression(ExpressionFunc::StrLength, DataType::Varchar).unwrap();
+        let result = expr
+            .eval_expr(&[&StringArray::from_slice(&[Some("é"), Some(""), None]).into()])
+            .unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::Int32(1));
+        assert_eq!(result.get(1).unwrap(), ScalarRefImpl::Int32(0));
+        assert!(result.get(2).is_none());
+
+        assert!(build_unary_expression(ExpressionFunc::StrUpper, DataType::Integer).is_err());
+    }
+
+    #[test]
+    fn test_build_type_of() {
+        let expr = build_unary_expression(ExpressionFunc::TypeOf, DataType::Integer).unwrap();
+        let result = expr
+            .eval_expr(&[&I32Array::from_slice(&[Some(1), None]).into()])
+            .unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::String("INTEGER"));
+        assert_eq!(result.get(1).unwrap(), ScalarRefImpl::String("INTEGER"));
+    }
+
+    #[test]
+    fn test_build_is_null() {
+        let input: ArrayImpl = StringArray::from_slice(&[None, Some("a")]).into();
+        let expr = build_unary_expression(ExpressionFunc::IsNull, DataType::Varchar).unwrap();
+        let result = expr.eval_expr(&[&input]).unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::Bool(true));
+        assert_eq!(result.get(1).unwrap(), ScalarRefImpl::Bool(false));
+
+        let expr = build_unary_expression(ExpressionFunc::IsNotNull, DataType::Varchar).unwrap();
+        let result = expr.eval_expr(&[&input]).unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::Bool(false));
+        assert_eq!(result.get(1).unwrap(), ScalarRefImpl::Bool(true));
+    }
+
     #[test]
     fn test_cmp_i16_f64() {
         let expr =
-            build_binary_expression(ExpressionFunc::CmpGe, DataType::SmallInt, DataType::Double);
+            build_binary_expression(ExpressionFunc::CmpGe, DataType::SmallInt, DataType::Double)
+                .unwrap();
 
         let result = expr
             .eval_expr(&[
@@ -171,4 +466,226 @@ mod tests {
         assert_eq!(result.get(0).unwrap(), ScalarRefImpl::Bool(true));
         assert_eq!(result.get(1).unwrap(), ScalarRefImpl::Bool(false));
     }
+
+    #[test]
+    fn test_build_arith() {
+        let expr =
+            build_binary_expression(ExpressionFunc::Add, DataType::SmallInt, DataType::Integer)
+                .unwrap();
+        let result = expr
+            .eval_expr(&[
+                &I16Array::from_slice(&[Some(1), Some(2), None]).into(),
+                &I32Array::from_slice(&[Some(100_000), Some(3), Some(4)]).into(),
+            ])
+            .unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::Int32(100_001));
+        assert_eq!(result.get(1).unwrap(), ScalarRefImpl::Int32(5));
+        assert!(result.get(2).is_none());
+
+        let expr = build_binary_expression(ExpressionFunc::Mul, DataType::Integer, DataType::Real)
+            .unwrap();
+        let result = expr
+            .eval_expr(&[
+                &I32Array::from_slice(&[Some(3)]).into(),
+                &F32Array::from_slice(&[Some(0.5)]).into(),
+            ])
+            .unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::Float64(1.5));
+
+        assert!(
+            build_binary_expression(ExpressionFunc::Sub, DataType::Varchar, DataType::Integer)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_build_decimal() {
+        let decimal = || DataType::Decimal {
+            scale: 2,
+            precision: 10,
+        };
+        let d = |s: &str| s.parse::<expr_common::Decimal>().unwrap();
+        let i1: ArrayImpl =
+            DecimalArray::from_slice(&[Some(d("1.0")), Some(d("1.5")), None]).into();
+        let i2: ArrayImpl =
+            DecimalArray::from_slice(&[Some(d("1.00")), Some(d("0.25")), Some(d("1"))]).into();
+
+        let expr = build_binary_expression(ExpressionFunc::Add, decimal(), decimal()).unwrap();
+        let result: DecimalArray = expr.eval_expr(&[&i1, &i2]).unwrap().try_into().unwrap();
+        assert_eq!(result.get(0).unwrap().to_string(), "2.00");
+        assert_eq!(result.get(1).unwrap().to_string(), "1.75");
+        assert!(result.get(2).is_none());
+
+        let expr = build_binary_expression(ExpressionFunc::Sub, decimal(), decimal()).unwrap();
+        let result: DecimalArray = expr.eval_expr(&[&i1, &i2]).unwrap().try_into().unwrap();
+        assert_eq!(result.get(0).unwrap().to_string(), "0.00");
+        assert_eq!(result.get(1).unwrap().to_string(), "1.25");
+
+        let expr = build_binary_expression(ExpressionFunc::CmpEq, decimal(), decimal()).unwrap();
+        let result = expr.eval_expr(&[&i1, &i2]).unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::Bool(true));
+        assert_eq!(result.get(1).unwrap(), ScalarRefImpl::Bool(false));
+
+        let expr = build_binary_expression(ExpressionFunc::CmpLe, decimal(), decimal()).unwrap();
+        let result = expr.eval_expr(&[&i1, &i2]).unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::Bool(false));
+        assert_eq!(result.get(1).unwrap(), ScalarRefImpl::Bool(false));
+
+        // Integers are cast to decimals before comparing.
+        let expr =
+            build_binary_expression(ExpressionFunc::CmpEq, DataType::Integer, decimal()).unwrap();
+        let result = expr
+            .eval_expr(&[&I32Array::from_slice(&[Some(1), Some(0), None]).into(), &i2])
+            .unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::Bool(true));
+        assert_eq!(result.get(1).unwrap(), ScalarRefImpl::Bool(false));
+        assert!(result.get(2).is_none());
+    }
+
+    #[test]
+    fn test_build_arith_overflow() {
+        let expr =
+            build_binary_expression(ExpressionFunc::Add, DataType::Integer, DataType::Integer)
+                .unwrap();
+        let result = expr.eval_expr(&[
+            &I32Array::from_slice(&[Some(1), Some(i32::MAX)]).into(),
+            &I32Array::from_slice(&[Some(1), Some(1)]).into(),
+        ]);
+        assert!(result.is_err());
+
+        // Widening to `i64` avoids the overflow.
+        let expr =
+            build_binary_expression(ExpressionFunc::Add, DataType::Integer, DataType::BigInt)
+                .unwrap();
+        let result = expr
+            .eval_expr(&[
+                &I32Array::from_slice(&[Some(i32::MAX)]).into(),
+                &I64Array::from_slice(&[Some(1)]).into(),
+            ])
+            .unwrap();
+        assert_eq!(
+            result.get(0).unwrap(),
+            ScalarRefImpl::Int64(i32::MAX as i64 + 1)
+        );
+    }
+
+    #[test]
+    fn test_build_div_by_zero() {
+        let expr =
+            build_binary_expression(ExpressionFunc::Div, DataType::Integer, DataType::Integer)
+                .unwrap();
+        let result = expr
+            .eval_expr(&[
+                &I32Array::from_slice(&[Some(7), None]).into(),
+                &I32Array::from_slice(&[Some(2), Some(0)]).into(),
+            ])
+            .unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::Int32(3));
+        assert!(result.get(1).is_none());
+
+        let result = expr.eval_expr(&[
+            &I32Array::from_slice(&[Some(7)]).into(),
+            &I32Array::from_slice(&[Some(0)]).into(),
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_build_logic() {
+        let i1: ArrayImpl = BoolArray::from_slice(&[Some(true), Some(false), None]).into();
+        let i2: ArrayImpl = BoolArray::from_slice(&[None, None, None]).into();
+
+        let expr =
+            build_binary_expression(ExpressionFunc::Or, DataType::Boolean, DataType::Boolean)
+                .unwrap();
+        let result = expr.eval_expr(&[&i1, &i2]).unwrap();
+        let result: &BoolArray = (&result).try_into().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(true), None, None]
+        );
+
+        let expr =
+            build_binary_expression(ExpressionFunc::And, DataType::Boolean, DataType::Boolean)
+                .unwrap();
+        let result = expr.eval_expr(&[&i1, &i2]).unwrap();
+        let result: &BoolArray = (&result).try_into().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![None, Some(false), None]
+        );
+
+        let expr = build_unary_expression(ExpressionFunc::Not, DataType::Boolean).unwrap();
+        let result = expr.eval_expr(&[&i1]).unwrap();
+        let result: &BoolArray = (&result).try_into().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(false), Some(true), None]
+        );
+
+        assert!(
+            build_binary_expression(ExpressionFunc::And, DataType::Boolean, DataType::Integer)
+                .is_err()
+        );
+        assert!(build_unary_expression(ExpressionFunc::Not, DataType::Integer).is_err());
+    }
+
+    #[test]
+    fn test_build_case() {
+        let expr = build_ternary_expression(
+            ExpressionFunc::Case,
+            DataType::Boolean,
+            DataType::Varchar,
+            DataType::Char { width: 1 },
+        )
+        .unwrap();
+        let result = expr
+            .eval_expr(&[
+                &BoolArray::from_slice(&[Some(true), Some(false), None]).into(),
+                &StringArray::from_slice(&[Some("a"), Some("b"), Some("c")]).into(),
+                &StringArray::from_slice(&[None, Some("y"), Some("z")]).into(),
+            ])
+            .unwrap();
+        assert_eq!(result.get(0).unwrap(), ScalarRefImpl::String("a"));
+        assert_eq!(result.get(1).unwrap(), ScalarRefImpl::String("y"));
+        assert!(result.get(2).is_none());
+
+        assert!(build_ternary_expression(
+            ExpressionFunc::Case,
+            DataType::Boolean,
+            DataType::Integer,
+            DataType::BigInt
+        )
+        .is_err());
+        assert!(build_ternary_expression(
+            ExpressionFunc::Case,
+            DataType::Integer,
+            DataType::Integer,
+            DataType::Integer
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_build_unsupported() {
+        assert!(build_binary_expression(
+            ExpressionFunc::CmpEq,
+            DataType::Boolean,
+            DataType::Integer
+        )
+        .is_err());
+        assert!(build_binary_expression(
+            ExpressionFunc::CmpLe,
+            DataType::Varchar,
+            DataType::Double
+        )
+        .is_err());
+        assert!(build_binary_expression(
+            ExpressionFunc::TypeOf,
+            DataType::Integer,
+            DataType::Integer
+        )
+        .is_err());
+        assert!(build_unary_expression(ExpressionFunc::CmpEq, DataType::Integer).is_err());
+    }
 }
@@ -4,8 +4,39 @@ use anyhow::Result;
 use itertools::Itertools;
 use quote::{format_ident, quote};
 
+/// Generate `FnArgsNExpression`, which propagates nulls automatically: the output is null if any
+/// input is null, and the function is only called on non-null inputs.
 pub fn generate_expression_template(param_number: usize) -> Result<String> {
-    let expr_template_name = format_ident!("FnArgs{}Expression", param_number);
+    generate_template(param_number, TemplateKind::Plain)
+}
+
+/// Generate `FnArgsNNullableExpression`, where the function takes `Option`s of all inputs and
+/// returns an `Option`, so that it fully controls the null handling.
+pub fn generate_nullable_expression_template(param_number: usize) -> Result<String> {
+    generate_template(param_number, TemplateKind::Nullable)
+}
+
+/// Generate `FnArgsNFallibleExpression`, which propagates nulls in the same way as
+/// `FnArgsNExpression`, but the function returns a `Result`, and the first error aborts the
+/// evaluation.
+pub fn generate_fallible_expression_template(param_number: usize) -> Result<String> {
+    generate_template(param_number, TemplateKind::Fallible)
+}
+
+/// How the generated expression calls the function.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TemplateKind {
+    Plain,
+    Nullable,
+    Fallible,
+}
+
+fn generate_template(param_number: usize, kind: TemplateKind) -> Result<String> {
+    let expr_template_name = match kind {
+        TemplateKind::Plain => format_ident!("FnArgs{}Expression", param_number),
+        TemplateKind::Nullable => format_ident!("FnArgs{}NullableExpression", param_number),
+        TemplateKind::Fallible => format_ident!("FnArgs{}FallibleExpression", param_number),
+    };
     let gp = (0..param_number)
         .map(|i| format_ident!("I{}", i + 1))
         .collect_vec();
@@ -13,6 +44,8 @@ pub fn generate_expression_template(param_number: usize) -> Result<String> {
         .map(|i| format_ident!("i{}", i + 1))
         .collect_vec();
     let position = 0..param_number;
+    // Inputs to check the length against `i1`.
+    let it_rest = &it[1..];
 
     let impl_before = quote! {
         #( #gp, )* O, F
@@ -22,12 +55,107 @@ pub fn generate_expression_template(param_number: usize) -> Result<String> {
         #expr_template_name<#( #gp, )* O, F>
     };
 
+    let func_bound = match kind {
+        TemplateKind::Plain => quote! {
+            F: Fn(
+                #( #gp::RefType<'_>, )*
+            ) -> O,
+        },
+        TemplateKind::Nullable => quote! {
+            F: Fn(
+                #( Option<#gp::RefType<'_>>, )*
+            ) -> Option<O>,
+        },
+        TemplateKind::Fallible => quote! {
+            F: Fn(
+                #( #gp::RefType<'_>, )*
+            ) -> Result<O>,
+        },
+    };
+
     let bounds = quote! {
         O: Scalar,
         #( #gp: Scalar, )*
-        F: Fn(
-            #( #gp::RefType<'_>, )*
-        ) -> O,
+        #func_bound
+    };
+
+    let push_output = match kind {
+        TemplateKind::Plain => quote! {
+            match ( #( #it, )* ) {
+                ( #( Some(#it), )* ) => builder.push(Some((self.func)(#( #it, )*).as_scalar_ref())),
+                _ => builder.push(None),
+            }
+        },
+        TemplateKind::Nullable => quote! {
+            let output = (self.func)(#( #it, )*);
+            builder.push(output.as_ref().map(|x| x.as_scalar_ref()));
+        },
+        TemplateKind::Fallible => quote! {
+            match ( #( #it, )* ) {
+                ( #( Some(#it), )* ) => builder.push(Some((self.func)(#( #it, )*)?.as_scalar_ref())),
+                _ => builder.push(None),
+            }
+        },
+    };
+
+    // When no input has nulls, values are passed to the function without matching on `Option`s
+    // for each row. Nullable functions always take `Option`s, so they don't have a fast path.
+    let eval_non_null = match kind {
+        TemplateKind::Plain => quote! {
+            if #( !#it.has_nulls() )&&* {
+                for ( #( #it ),* ) in itertools::izip!( #( #it.iter().flatten() ),* ) {
+                    builder.push(Some((self.func)(#( #it, )*).as_scalar_ref()));
+                }
+                return Ok(builder.finish().into());
+            }
+        },
+        TemplateKind::Nullable => quote! {},
+        TemplateKind::Fallible => quote! {
+            if #( !#it.has_nulls() )&&* {
+                for ( #( #it ),* ) in itertools::izip!( #( #it.iter().flatten() ),* ) {
+                    builder.push(Some((self.func)(#( #it, )*)?.as_scalar_ref()));
+                }
+                return Ok(builder.finish().into());
+            }
+        },
+    };
+
+    let eval_constant = match kind {
+        TemplateKind::Plain => quote! {
+            match ( #( #it, )* ) {
+                ( #( Some(#it), )* ) => Some((self.func)(#( #it, )*)),
+                _ => None,
+            }
+        },
+        TemplateKind::Nullable => quote! {
+            (self.func)(#( #it, )*)
+        },
+        TemplateKind::Fallible => quote! {
+            match ( #( #it, )* ) {
+                ( #( Some(#it), )* ) => Some((self.func)(#( #it, )*)?),
+                _ => None,
+            }
+        },
+    };
+
+    // `col + 1` is the most common case of a constant operand, so binary expressions get a
+    // shortcut for it.
+    let eval_scalar_right = if param_number == 2 {
+        quote! {
+            /// Evaluate the expression with an array on the left and a constant on the right.
+            /// The constant is not materialized as an array.
+            pub fn eval_batch_scalar_right(&self, i1: &ArrayImpl, i2: ScalarRefImpl<'_>) -> Result<ArrayImpl>
+            where
+                for<'a> I1::RefType<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>,
+                for<'a> I2::RefType<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>,
+            {
+                let len = i1.len();
+                let output = self.eval_inputs(Input::Array(i1), Input::Constant(Some(i2), len))?;
+                Ok(output.into_array())
+            }
+        }
+    } else {
+        quote! {}
     };
 
     let extra_bounds = quote! {
@@ -72,19 +200,57 @@ pub fn generate_expression_template(param_number: usize) -> Result<String> {
                     let #it: &#gp::ArrayType = #it.try_into()?;
                 )*
                 #(
-                    assert_eq!(i1.len(), #it.len(), "array length mismatch");
+                    if i1.len() != #it_rest.len() {
+                        return Err(anyhow!("array length mismatch: {} and {}", i1.len(), #it_rest.len()));
+                    }
                 )*
                 let mut builder = <O::ArrayType as Array>::Builder::with_capacity(i1.len());
+                #eval_non_null
                 for ( #( #it ),* ) in itertools::izip!(
                     #( #it.iter() ),*
                 ) {
-                    match ( #( #it, )* ) {
-                        ( #( Some(#it), )* ) => builder.push(Some((self.func)(#( #it, )*).as_scalar_ref())),
-                        _ => builder.push(None),
-                    }
+                    #push_output
                 }
                 Ok(builder.finish().into())
             }
+
+            /// Evaluate the expression with inputs that may be constants.
+            ///
+            /// If all inputs are constants, the function is called only once, and the output is
+            /// also a constant. Otherwise, constants are repeated for each row without being
+            /// materialized as arrays.
+            pub fn eval_inputs(&self, #( #it: Input<'_>),*) -> Result<Output>
+            where
+                #( for<'a> #gp::RefType<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>, )*
+            {
+                let len = i1.len();
+                #(
+                    if len != #it_rest.len() {
+                        return Err(anyhow!("array length mismatch: {} and {}", len, #it_rest.len()));
+                    }
+                )*
+                if let ( #( Input::Constant(#it, _), )* ) = ( #( #it, )* ) {
+                    #(
+                        let #it = constant_to_scalar::<#gp>(#it)?;
+                    )*
+                    let output: Option<O> = #eval_constant;
+                    return Ok(Output::Constant {
+                        value: output.map(Into::into),
+                        physical_type: O::PHYSICAL_TYPE,
+                        len,
+                    });
+                }
+                #(
+                    let #it = input_iter::<#gp>(#it)?;
+                )*
+                let mut builder = <O::ArrayType as Array>::Builder::with_capacity(len);
+                for ( #( #it ),* ) in itertools::izip!( #( #it ),* ) {
+                    #push_output
+                }
+                Ok(Output::Array(builder.finish().into()))
+            }
+
+            #eval_scalar_right
         }
 
         /// Blanket [`Expression`] implementation for `ArgsNExpression`
@@ -119,4 +285,30 @@ mod tests {
     fn test_generate_binary_expression() {
         println!("{}", generate_expression_template(2).unwrap());
     }
+
+    #[test]
+    fn test_generate_nullable_binary_expression() {
+        let content = generate_nullable_expression_template(2).unwrap();
+        assert!(content.contains("pub struct FnArgs2NullableExpression"));
+    }
+
+    #[test]
+    fn test_generate_fallible_binary_expression() {
+        let content = generate_fallible_expression_template(2).unwrap();
+        assert!(content.contains("pub struct FnArgs2FallibleExpression"));
+    }
+
+    #[test]
+    fn test_generate_many_args() {
+        for generate in [
+            generate_expression_template,
+            generate_nullable_expression_template,
+            generate_fallible_expression_template,
+        ] {
+            let content = generate(8).unwrap();
+            syn::parse_file(&content).unwrap();
+            assert!(content.contains("i8: &ArrayImpl"));
+            assert!(content.contains("if i1.len() != i8.len()"));
+        }
+    }
 }
@@ -4,20 +4,42 @@ use std::fmt::Write;
 
 use anyhow::Result;
 
+/// Expressions are generated for 1 to `MAX_ARGS` arguments. All of them should be re-exported in
+/// `lib.rs`.
+const MAX_ARGS: usize = 8;
+
 fn main() -> Result<()> {
     println!("cargo:rerun-if-changed=../expr-template-impl");
 
     let mut gen_header = String::new();
 
     writeln!(gen_header, "#![allow(dead_code)]")?;
+    writeln!(gen_header, "#![allow(clippy::too_many_arguments)]")?;
+    writeln!(gen_header, "#![allow(clippy::type_complexity)]")?;
     writeln!(gen_header, "#![allow(unused_parens)]")?;
     writeln!(gen_header)?;
 
-    for i in 1..=5 {
+    for i in 1..=MAX_ARGS {
         let content = expr_template_impl::generate_expression_template(i)?;
         std::fs::write(format!("src/gen/fn_args_{}_expression.rs", i), content)?;
         writeln!(gen_header, "mod fn_args_{}_expression;", i)?;
         writeln!(gen_header, "pub use fn_args_{}_expression::*;", i)?;
+
+        let content = expr_template_impl::generate_nullable_expression_template(i)?;
+        std::fs::write(
+            format!("src/gen/fn_args_{}_nullable_expression.rs", i),
+            content,
+        )?;
+        writeln!(gen_header, "mod fn_args_{}_nullable_expression;", i)?;
+        writeln!(gen_header, "pub use fn_args_{}_nullable_expression::*;", i)?;
+
+        let content = expr_template_impl::generate_fallible_expression_template(i)?;
+        std::fs::write(
+            format!("src/gen/fn_args_{}_fallible_expression.rs", i),
+            content,
+        )?;
+        writeln!(gen_header, "mod fn_args_{}_fallible_expression;", i)?;
+        writeln!(gen_header, "pub use fn_args_{}_fallible_expression::*;", i)?;
     }
 
     std::fs::write("src/gen/mod.rs", gen_header)?;
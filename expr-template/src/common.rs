@@ -1,9 +1,43 @@
 // Copyright 2022 Alex Chi. Licensed under Apache-2.0.
 
+use std::iter::{Repeat, Take};
 pub use std::marker::PhantomData;
 
 pub use anyhow::{anyhow, Result};
-pub use expr_common::array::{Array, ArrayBuilder, ArrayImpl};
-pub use expr_common::expr::Expression;
-pub use expr_common::scalar::Scalar;
+pub use expr_common::array::{Array, ArrayBuilder, ArrayImpl, ArrayIterator};
+pub use expr_common::expr::{Expression, Input, Output};
+pub use expr_common::scalar::{Scalar, ScalarRefImpl};
 pub use expr_common::TypeMismatch;
+use itertools::Either;
+
+/// Convert a constant input into the scalar type `S`.
+pub fn constant_to_scalar<S: Scalar>(
+    value: Option<ScalarRefImpl<'_>>,
+) -> Result<Option<S::RefType<'_>>>
+where
+    for<'a> S::RefType<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>,
+{
+    Ok(value.map(TryInto::try_into).transpose()?)
+}
+
+/// Iterate over an input of scalar type `S`. A constant is repeated for the length of the input
+/// without being materialized.
+#[allow(clippy::type_complexity)]
+pub fn input_iter<S: Scalar>(
+    input: Input<'_>,
+) -> Result<Either<ArrayIterator<'_, S::ArrayType>, Take<Repeat<Option<S::RefType<'_>>>>>>
+where
+    for<'a> S::RefType<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>,
+    for<'a> &'a S::ArrayType: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+{
+    match input {
+        Input::Array(array) => {
+            let array: &S::ArrayType = array.try_into()?;
+            Ok(Either::Left(array.iter()))
+        }
+        Input::Constant(value, len) => {
+            let value = constant_to_scalar::<S>(value)?;
+            Ok(Either::Right(std::iter::repeat(value).take(len)))
+        }
+    }
+}
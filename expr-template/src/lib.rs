@@ -6,6 +6,15 @@ mod common;
 mod gen;
 
 pub use gen::{
-    FnArgs1Expression as UnaryExpression, FnArgs2Expression as BinaryExpression, FnArgs3Expression,
-    FnArgs4Expression, FnArgs5Expression,
+    FnArgs1Expression as UnaryExpression, FnArgs1FallibleExpression as FallibleUnaryExpression,
+    FnArgs1NullableExpression as NullableUnaryExpression, FnArgs2Expression as BinaryExpression,
+    FnArgs2FallibleExpression as FallibleBinaryExpression,
+    FnArgs2NullableExpression as NullableBinaryExpression, FnArgs3Expression as TernaryExpression,
+    FnArgs3Expression, FnArgs3FallibleExpression as FallibleTernaryExpression,
+    FnArgs3NullableExpression as NullableTernaryExpression, FnArgs4Expression,
+    FnArgs4FallibleExpression, FnArgs4NullableExpression, FnArgs5Expression,
+    FnArgs5FallibleExpression, FnArgs5NullableExpression, FnArgs6Expression,
+    FnArgs6FallibleExpression, FnArgs6NullableExpression, FnArgs7Expression,
+    FnArgs7FallibleExpression, FnArgs7NullableExpression, FnArgs8Expression,
+    FnArgs8FallibleExpression, FnArgs8NullableExpression,
 };